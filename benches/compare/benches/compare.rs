@@ -0,0 +1,79 @@
+//! This crate against serde_json, parsing into and serializing from each
+//! one's own `Value`, on the corpora of `benches/parse.rs`.
+//!
+//! Run with `cargo bench` from this directory. Criterion reports the time per
+//! pass and the throughput, in bytes of JSON text read or written, and
+//! compares with the previous run.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+const CORPORA: [(&str, &str); 3] = [
+    ("records", include_str!("../../fixtures/records.json")),
+    ("nested", include_str!("../../fixtures/nested.json")),
+    ("strings", include_str!("../../fixtures/strings.json")),
+];
+
+const TINY_DOCUMENTS: usize = 100_000;
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for (corpus, input) in CORPORA {
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("json", corpus), input, |b, input| {
+            b.iter(|| json::parse(black_box(input)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("serde_json", corpus), input, |b, input| {
+            b.iter(|| serde_json::from_str::<serde_json::Value>(black_box(input)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn to_string(c: &mut Criterion) {
+    let mut group = c.benchmark_group("to_string");
+    for (corpus, input) in CORPORA {
+        let value = json::parse(input).unwrap();
+        let serde_value: serde_json::Value = serde_json::from_str(input).unwrap();
+        group.throughput(Throughput::Bytes(value.to_string().len() as u64));
+        group.bench_with_input(BenchmarkId::new("json", corpus), &value, |b, value| {
+            b.iter(|| value.to_string())
+        });
+        group.bench_with_input(
+            BenchmarkId::new("serde_json", corpus),
+            &serde_value,
+            |b, value| b.iter(|| serde_json::to_string(value).unwrap()),
+        );
+    }
+    group.finish();
+}
+
+/// Many small documents parsed one at a time, like requests to a server.
+fn tiny(c: &mut Criterion) {
+    let tiny: Vec<String> = (0..TINY_DOCUMENTS)
+        .map(|i| format!(r#"{{"id":{},"name":"user{}","active":true}}"#, i, i))
+        .collect();
+    let mut group = c.benchmark_group("tiny");
+    group.throughput(Throughput::Bytes(
+        tiny.iter().map(String::len).sum::<usize>() as u64,
+    ));
+    group.bench_function("json", |b| {
+        b.iter(|| {
+            for input in &tiny {
+                black_box(json::parse(input).unwrap());
+            }
+        })
+    });
+    group.bench_function("serde_json", |b| {
+        b.iter(|| {
+            for input in &tiny {
+                black_box(serde_json::from_str::<serde_json::Value>(input).unwrap());
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parse, to_string, tiny);
+criterion_main!(benches);
@@ -0,0 +1,134 @@
+//! Parsing and serialization throughput on the corpora in `fixtures/`.
+//!
+//! Run with `cargo bench`, or `cargo bench -- records` to run only the cases
+//! whose name contains `records`. Each case runs for about a second after a
+//! warm-up, and reports the mean time per pass and the throughput, counted in
+//! bytes of JSON text read or written.
+//!
+//! The corpora are:
+//!
+//! - `records.json`: a big array of small, flat-ish objects, like an API
+//!   response or a log dump.
+//! - `nested.json`: arrays of documents nested 100 levels deep.
+//! - `strings.json`: one object whose values are long strings, mixing ASCII,
+//!   multi-byte characters and escapes.
+//! - `tiny`: 100,000 separate tiny objects, generated rather than read from a
+//!   file, parsed one at a time like requests to a server.
+//!
+//! `benches/compare` runs the same corpora through serde_json for a
+//! baseline. It is a crate of its own, so that criterion and serde_json don't
+//! become dependencies of this one. `cargo bench --bench allocs` counts
+//! allocations.
+
+use std::{
+    cell::RefCell,
+    env,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use json::Parser;
+
+const CORPORA: [(&str, &str); 3] = [
+    ("records", include_str!("fixtures/records.json")),
+    ("nested", include_str!("fixtures/nested.json")),
+    ("strings", include_str!("fixtures/strings.json")),
+];
+
+const TINY_DOCUMENTS: usize = 100_000;
+
+const WARM_UP: Duration = Duration::from_millis(200);
+const MEASURE: Duration = Duration::from_secs(1);
+
+fn main() {
+    let filters: Vec<String> = env::args()
+        .skip(1)
+        .filter(|a| !a.starts_with('-'))
+        .collect();
+    let selected = |name: &str| filters.is_empty() || filters.iter().any(|f| name.contains(&**f));
+
+    for (corpus, input) in CORPORA {
+        let value = json::parse(input).unwrap();
+        let compact = value.to_string().len();
+        let pretty = json::to_string_pretty(&value, 2).len();
+        let parser = RefCell::new(Parser::new());
+
+        // Each case with the length of the text it reads or writes.
+        let cases: [(&str, usize, &dyn Fn()); 6] = [
+            ("parse", input.len(), &|| {
+                drop(black_box(json::parse(input)))
+            }),
+            ("parse_reusing", input.len(), &|| {
+                drop(black_box(parser.borrow_mut().parse_reusing(input)))
+            }),
+            ("parse_borrowed", input.len(), &|| {
+                drop(black_box(json::parse_borrowed(input)))
+            }),
+            ("validate", input.len(), &|| {
+                drop(black_box(json::validate(input)))
+            }),
+            ("to_string", compact, &|| drop(black_box(value.to_string()))),
+            ("to_string_pretty", pretty, &|| {
+                drop(black_box(json::to_string_pretty(&value, 2)))
+            }),
+        ];
+        for (case, bytes, run) in cases {
+            let name = format!("{}/{}", corpus, case);
+            if !selected(&name) {
+                continue;
+            }
+            report(&name, bytes, measure(run));
+        }
+    }
+
+    let tiny: Vec<String> = (0..TINY_DOCUMENTS)
+        .map(|i| format!(r#"{{"id":{},"name":"user{}","active":true}}"#, i, i))
+        .collect();
+    let bytes = tiny.iter().map(String::len).sum();
+    let parser = RefCell::new(Parser::new());
+    let cases: [(&str, &dyn Fn()); 2] = [
+        ("tiny/parse", &|| {
+            for input in &tiny {
+                drop(black_box(json::parse(input)));
+            }
+        }),
+        ("tiny/parse_reusing", &|| {
+            let mut parser = parser.borrow_mut();
+            for input in &tiny {
+                drop(black_box(parser.parse_reusing(input)));
+            }
+        }),
+    ];
+    for (name, run) in cases {
+        if selected(name) {
+            report(name, bytes, measure(run));
+        }
+    }
+}
+
+/// Runs `f` repeatedly and gives the mean time of one run.
+fn measure(f: &dyn Fn()) -> Duration {
+    let start = Instant::now();
+    while start.elapsed() < WARM_UP {
+        f();
+    }
+
+    let mut runs = 0;
+    let start = Instant::now();
+    while start.elapsed() < MEASURE {
+        f();
+        runs += 1;
+    }
+
+    start.elapsed() / runs
+}
+
+fn report(name: &str, bytes: usize, time: Duration) {
+    let throughput = bytes as f64 / time.as_secs_f64() / 1e6;
+    println!(
+        "{:<28} {:>10.3} ms {:>10.1} MB/s",
+        name,
+        time.as_secs_f64() * 1e3,
+        throughput
+    );
+}
@@ -0,0 +1,48 @@
+//! Feeds arbitrary bytes to the parser, which must return `Ok` or `Err` but
+//! never panic. Run from the repository root with
+//! `cargo +nightly fuzz run parse fuzz/corpus/parse`.
+#![no_main]
+
+use json::{DuplicateKeys, Parser, Value};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let strict = Parser::new();
+    let lenient = Parser::new()
+        .allow_comments(true)
+        .allow_trailing_commas(true)
+        .allow_non_finite(true)
+        .raw_numbers(true)
+        .allow_unquoted_keys(true)
+        .allow_single_quotes(true)
+        .duplicate_keys(DuplicateKeys::Error)
+        .with_max_depth(16);
+
+    for parser in &[strict, lenient] {
+        let parsed = parser.parse_bytes(data);
+        let streamed = parser.parse_reader(data);
+        assert_eq!(parsed.is_ok(), streamed.is_ok());
+
+        if let Ok(input) = std::str::from_utf8(data) {
+            let _ = parser.parse_borrowed(input);
+            let _ = parser.events(input).count();
+        }
+
+        // whatever parses must survive a round trip, except for the
+        // non-finite numbers JSON can't represent
+        if let Ok(value) = parsed {
+            if is_finite(&value) {
+                assert_eq!(json::parse(&value.to_string()).ok(), Some(value));
+            }
+        }
+    }
+});
+
+fn is_finite(value: &Value) -> bool {
+    match value {
+        Value::Number(n) => n.is_finite(),
+        Value::Array(vec) => vec.iter().all(is_finite),
+        Value::Object(map) => map.values().all(is_finite),
+        _ => true,
+    }
+}
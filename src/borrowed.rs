@@ -0,0 +1,74 @@
+use alloc::{borrow::Cow, vec::Vec};
+
+use crate::{Map, Value};
+
+/// A parsed JSON value that borrows its strings from the input where it can.
+///
+/// Strings and keys without escape sequences point straight into the parsed
+/// text, so only the ones that had to be unescaped are allocated. Objects keep
+/// their members in document order, and looking one up scans them.
+#[derive(Debug, Clone)]
+pub enum BorrowedValue<'a> {
+    Object(Vec<(Cow<'a, str>, BorrowedValue<'a>)>),
+    Array(Vec<BorrowedValue<'a>>),
+    String(Cow<'a, str>),
+    Number(f64),
+    Integer(i64),
+    RawNumber(Cow<'a, str>),
+    True,
+    False,
+    Null,
+}
+
+impl<'a> BorrowedValue<'a> {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            BorrowedValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Looks up an object member, giving `None` when the member is missing or
+    /// the value isn't an object.
+    pub fn get(&self, key: &str) -> Option<&BorrowedValue<'a>> {
+        match self {
+            BorrowedValue::Object(members) => members
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Looks up an array element, giving `None` when the index is out of bounds
+    /// or the value isn't an array.
+    pub fn get_index(&self, index: usize) -> Option<&BorrowedValue<'a>> {
+        match self {
+            BorrowedValue::Array(vec) => vec.get(index),
+            _ => None,
+        }
+    }
+
+    /// Copies the borrowed strings to build an owned `Value`.
+    pub fn into_owned(self) -> Value {
+        match self {
+            BorrowedValue::Object(members) => {
+                let mut map = Map::new();
+                for (key, value) in members {
+                    map.insert(&*key, value.into_owned());
+                }
+                Value::Object(map)
+            }
+            BorrowedValue::Array(vec) => {
+                Value::Array(vec.into_iter().map(BorrowedValue::into_owned).collect())
+            }
+            BorrowedValue::String(s) => Value::String(s.into_owned()),
+            BorrowedValue::Number(n) => Value::Number(n),
+            BorrowedValue::Integer(n) => Value::Integer(n),
+            BorrowedValue::RawNumber(s) => Value::RawNumber(s.into_owned()),
+            BorrowedValue::True => Value::True,
+            BorrowedValue::False => Value::False,
+            BorrowedValue::Null => Value::Null,
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::{self, Write};
+
+use crate::{ser::write_string, value::raw_number_value, Value};
+
+/// Serializes the value following the JSON Canonicalization Scheme (RFC 8785),
+/// giving the same bytes for any two equal documents.
+///
+/// Object members are sorted by the UTF-16 code units of their keys, numbers
+/// are written the way ECMAScript prints them, and there is no whitespace.
+/// Like every JCS number, integers are treated as doubles, so ones beyond 2^53
+/// get rounded. Non-finite numbers, which JCS forbids, are written as `null`.
+///
+/// The examples of the RFC, for numbers and strings (§3.2.4) and for the
+/// order of keys (§3.2.3):
+///
+/// ```
+/// let value = json::parse(
+///     r#"{
+///         "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+///         "string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/",
+///         "literals": [null, true, false]
+///     }"#,
+/// )
+/// .unwrap();
+/// assert_eq!(
+///     json::to_canonical_string(&value),
+///     concat!(
+///         r#"{"literals":[null,true,false],"#,
+///         r#""numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"#,
+///         r#""string":"€$\u000f\nA'B\"\\\\\"/"}"#,
+///     ),
+/// );
+///
+/// let value = json::parse(
+///     r#"{
+///         "\u20ac": "Euro Sign",
+///         "\r": "Carriage Return",
+///         "\ufb33": "Hebrew Letter Dalet With Dagesh",
+///         "1": "One",
+///         "\ud83d\ude00": "Emoji: Grinning Face",
+///         "\u0080": "Control",
+///         "\u00f6": "Latin Small Letter O With Diaeresis"
+///     }"#,
+/// )
+/// .unwrap();
+/// assert_eq!(
+///     json::to_canonical_string(&value),
+///     concat!(
+///         r#"{"\r":"Carriage Return","1":"One","#,
+///         "\"\u{80}\":\"Control\",",
+///         "\"\u{f6}\":\"Latin Small Letter O With Diaeresis\",",
+///         "\"\u{20ac}\":\"Euro Sign\",",
+///         "\"\u{1f600}\":\"Emoji: Grinning Face\",",
+///         "\"\u{fb33}\":\"Hebrew Letter Dalet With Dagesh\"}",
+///     ),
+/// );
+/// ```
+pub fn to_canonical_string(value: &Value) -> String {
+    let mut out = String::new();
+    // writing into a String cannot fail
+    write_canonical(&mut out, value).unwrap();
+    out
+}
+
+fn write_canonical<W: Write>(w: &mut W, value: &Value) -> fmt::Result {
+    match value {
+        Value::Object(map) => {
+            let mut members: Vec<_> = map.iter().collect();
+            members.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+
+            w.write_char('{')?;
+            for (i, (key, value)) in members.into_iter().enumerate() {
+                if i > 0 {
+                    w.write_char(',')?;
+                }
+                write_string(w, key)?;
+                w.write_char(':')?;
+                write_canonical(w, value)?;
+            }
+            w.write_char('}')
+        }
+        Value::Array(vec) => {
+            w.write_char('[')?;
+            for (i, value) in vec.iter().enumerate() {
+                if i > 0 {
+                    w.write_char(',')?;
+                }
+                write_canonical(w, value)?;
+            }
+            w.write_char(']')
+        }
+        Value::Number(n) => write_es_number(w, *n),
+        Value::Integer(n) => write_es_number(w, *n as f64),
+        Value::RawNumber(s) => write_canonical(w, &raw_number_value(s)),
+        value => write!(w, "{}", value),
+    }
+}
+
+/// Formats a double like ECMAScript's `Number.prototype.toString`.
+fn write_es_number<W: Write>(w: &mut W, n: f64) -> fmt::Result {
+    if !n.is_finite() {
+        return w.write_str("null");
+    }
+    if n == 0.0 {
+        // this covers -0 as well
+        return w.write_char('0');
+    }
+    if n < 0.0 {
+        w.write_char('-')?;
+    }
+
+    // `{:e}` gives the shortest digits that round-trip, as ECMAScript requires
+    let sci = format!("{:e}", n.abs());
+    let (mantissa, exponent) = sci.split_at(sci.find('e').unwrap_or(sci.len()));
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let k = digits.len() as i32;
+    // the value is 0.<digits> * 10^n
+    let n = exponent[1..].parse::<i32>().unwrap_or(0) + 1;
+
+    if k <= n && n <= 21 {
+        w.write_str(&digits)?;
+        for _ in 0..n - k {
+            w.write_char('0')?;
+        }
+        Ok(())
+    } else if 0 < n && n <= 21 {
+        let (int, frac) = digits.split_at(n as usize);
+        write!(w, "{}.{}", int, frac)
+    } else if -6 < n && n <= 0 {
+        w.write_str("0.")?;
+        for _ in 0..-n {
+            w.write_char('0')?;
+        }
+        w.write_str(&digits)
+    } else {
+        let (first, rest) = digits.split_at(1);
+        w.write_str(first)?;
+        if !rest.is_empty() {
+            write!(w, ".{}", rest)?;
+        }
+        let sign = if n - 1 < 0 { '-' } else { '+' };
+        write!(w, "e{}{}", sign, (n - 1).abs())
+    }
+}
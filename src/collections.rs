@@ -0,0 +1,6 @@
+// Hash maps need std for their random seeds, so without it lookups go through
+// B-trees instead.
+#[cfg(not(feature = "std"))]
+pub(crate) use alloc::collections::{BTreeMap as LookupMap, BTreeSet as LookupSet};
+#[cfg(feature = "std")]
+pub(crate) use std::collections::{HashMap as LookupMap, HashSet as LookupSet};
@@ -0,0 +1,195 @@
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{convert::TryFrom, fmt};
+#[cfg(feature = "std")]
+use std::{collections::HashMap, error::Error};
+
+use crate::{
+    pointer::escape,
+    value::{float_to_int, raw_number_value},
+    Value,
+};
+
+/// Converts a `Value` into a Rust type, see `Value::from_json`.
+pub trait FromJson: Sized {
+    fn from_json(value: &Value) -> Result<Self, TypeError>;
+}
+
+/// A value that doesn't have the type `FromJson` expected.
+///
+/// `path` is the JSON Pointer of the value, relative to the one the conversion
+/// started from, e.g. `/servers/0/port`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeError {
+    /// The Rust type being converted to, e.g. `u16`.
+    pub expected: &'static str,
+    /// What the value turned out to be, e.g. `string`.
+    pub found: &'static str,
+    pub path: String,
+}
+
+impl TypeError {
+    fn new(expected: &'static str, value: &Value) -> Self {
+        TypeError {
+            expected,
+            found: value.type_name(),
+            path: String::new(),
+        }
+    }
+
+    /// Puts the error inside the member or element `token` of a container.
+    fn within(mut self, token: &str) -> Self {
+        self.path.insert_str(0, &escape(token));
+        self.path.insert(0, '/');
+        self
+    }
+}
+
+/// Formats as e.g. `expected u16, found string at /server/port`.
+impl fmt::Display for TypeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected {}, found {}", self.expected, self.found)?;
+        if !self.path.is_empty() {
+            write!(f, " at {}", self.path)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for TypeError {}
+
+impl Value {
+    /// Converts the value into any type that implements `FromJson`.
+    ///
+    /// ```
+    /// let value = json::parse(r#"{"server": {"port": 8080}}"#).unwrap();
+    /// let port: u16 = value["server"]["port"].from_json().unwrap();
+    /// assert_eq!(port, 8080);
+    /// ```
+    pub fn from_json<T: FromJson>(&self) -> Result<T, TypeError> {
+        T::from_json(self)
+    }
+}
+
+impl FromJson for Value {
+    fn from_json(value: &Value) -> Result<Self, TypeError> {
+        Ok(value.clone())
+    }
+}
+
+impl FromJson for bool {
+    fn from_json(value: &Value) -> Result<Self, TypeError> {
+        value.as_bool().ok_or_else(|| TypeError::new("bool", value))
+    }
+}
+
+impl FromJson for String {
+    fn from_json(value: &Value) -> Result<Self, TypeError> {
+        match value.as_str() {
+            Some(s) => Ok(s.to_string()),
+            None => Err(TypeError::new("String", value)),
+        }
+    }
+}
+
+impl FromJson for f64 {
+    fn from_json(value: &Value) -> Result<Self, TypeError> {
+        value.as_f64().ok_or_else(|| TypeError::new("f64", value))
+    }
+}
+
+impl FromJson for f32 {
+    fn from_json(value: &Value) -> Result<Self, TypeError> {
+        match value.as_f64() {
+            Some(n) => Ok(n as f32),
+            None => Err(TypeError::new("f32", value)),
+        }
+    }
+}
+
+// whole floats such as `8080.0` count as integers too, the same as `==` does
+macro_rules! impl_from_json_for_integers {
+    ($($ty:ident)*) => {
+        $(
+            impl FromJson for $ty {
+                fn from_json(value: &Value) -> Result<Self, TypeError> {
+                    if let Value::RawNumber(s) = value {
+                        return Self::from_json(&raw_number_value(s));
+                    }
+                    let int = match *value {
+                        Value::Integer(n) => Some(n),
+                        Value::Number(n) => float_to_int(n),
+                        _ => None,
+                    };
+                    let int = int.ok_or_else(|| TypeError::new(stringify!($ty), value))?;
+
+                    $ty::try_from(int).map_err(|_| TypeError {
+                        found: "number out of range",
+                        ..TypeError::new(stringify!($ty), value)
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_from_json_for_integers!(i8 i16 i32 i64 isize u8 u16 u32 u64 usize);
+
+/// `null` becomes `None`, anything else has to convert to `T`.
+impl<T: FromJson> FromJson for Option<T> {
+    fn from_json(value: &Value) -> Result<Self, TypeError> {
+        match value {
+            Value::Null => Ok(None),
+            _ => T::from_json(value).map(Some),
+        }
+    }
+}
+
+impl<T: FromJson> FromJson for Vec<T> {
+    fn from_json(value: &Value) -> Result<Self, TypeError> {
+        let vec = value
+            .as_array()
+            .ok_or_else(|| TypeError::new("Vec", value))?;
+
+        vec.iter()
+            .enumerate()
+            .map(|(i, element)| T::from_json(element).map_err(|err| err.within(&i.to_string())))
+            .collect()
+    }
+}
+
+impl<T: FromJson> FromJson for BTreeMap<String, T> {
+    fn from_json(value: &Value) -> Result<Self, TypeError> {
+        let map = value
+            .as_object()
+            .ok_or_else(|| TypeError::new("BTreeMap", value))?;
+
+        map.iter()
+            .map(|(key, member)| match T::from_json(member) {
+                Ok(member) => Ok((key.to_string(), member)),
+                Err(err) => Err(err.within(key)),
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: FromJson> FromJson for HashMap<String, T> {
+    fn from_json(value: &Value) -> Result<Self, TypeError> {
+        let map = value
+            .as_object()
+            .ok_or_else(|| TypeError::new("HashMap", value))?;
+
+        map.iter()
+            .map(|(key, member)| match T::from_json(member) {
+                Ok(member) => Ok((key.to_string(), member)),
+                Err(err) => Err(err.within(key)),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,93 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{pointer::escape, Value};
+
+/// A difference between two documents, located by a JSON Pointer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    Added {
+        path: String,
+        value: Value,
+    },
+    Removed {
+        path: String,
+        value: Value,
+    },
+    Modified {
+        path: String,
+        old: Value,
+        new: Value,
+    },
+}
+
+/// Lists the changes that turn `a` into `b`.
+///
+/// Objects are compared member by member and arrays element by element, by
+/// index. Any other difference, including a value changing type, is reported
+/// as a single modification holding both values.
+pub fn diff(a: &Value, b: &Value) -> Vec<Change> {
+    let mut changes = Vec::new();
+    diff_at(&mut String::new(), a, b, &mut changes);
+    changes
+}
+
+fn diff_at(path: &mut String, a: &Value, b: &Value, changes: &mut Vec<Change>) {
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => {
+            for (key, old) in a.iter() {
+                let len = push_segment(path, &escape(key));
+                match b.get(key) {
+                    Some(new) => diff_at(path, old, new, changes),
+                    None => changes.push(Change::Removed {
+                        path: path.clone(),
+                        value: old.clone(),
+                    }),
+                }
+                path.truncate(len);
+            }
+            for (key, new) in b.iter().filter(|(key, _)| !a.contains_key(key)) {
+                let len = push_segment(path, &escape(key));
+                changes.push(Change::Added {
+                    path: path.clone(),
+                    value: new.clone(),
+                });
+                path.truncate(len);
+            }
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            for i in 0..a.len().max(b.len()) {
+                let len = push_segment(path, &i.to_string());
+                match (a.get(i), b.get(i)) {
+                    (Some(old), Some(new)) => diff_at(path, old, new, changes),
+                    (Some(old), None) => changes.push(Change::Removed {
+                        path: path.clone(),
+                        value: old.clone(),
+                    }),
+                    (None, Some(new)) => changes.push(Change::Added {
+                        path: path.clone(),
+                        value: new.clone(),
+                    }),
+                    (None, None) => unreachable!(),
+                }
+                path.truncate(len);
+            }
+        }
+        _ if a == b => {}
+        _ => changes.push(Change::Modified {
+            path: path.clone(),
+            old: a.clone(),
+            new: b.clone(),
+        }),
+    }
+}
+
+/// Appends `/segment` to the path, returning the length to truncate back to.
+pub(crate) fn push_segment(path: &mut String, segment: &str) -> usize {
+    let len = path.len();
+    path.push('/');
+    path.push_str(segment);
+    len
+}
@@ -0,0 +1,282 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+/// The reason a document failed to parse.
+#[derive(Debug)]
+pub enum ErrorKind {
+    /// A character that can't appear where it was found. JSON numbers are
+    /// written with ASCII digits only, so other Unicode digits fail with this.
+    ///
+    /// ```
+    /// use json::ErrorKind;
+    ///
+    /// let err = json::parse("[\u{9eb}]").unwrap_err();
+    /// assert!(matches!(err.kind, ErrorKind::UnexpectedChar('\u{9eb}')));
+    /// let err = json::parse("1\u{b2}").unwrap_err();
+    /// assert!(matches!(err.kind, ErrorKind::TrailingData));
+    /// ```
+    UnexpectedChar(char),
+    /// The input ended before the document did. Any incomplete prefix of a
+    /// valid document fails with this, so a caller reading the input in
+    /// pieces can wait for more of it. Only an unclosed block comment gives
+    /// `UnterminatedComment` instead, a lone `-` gives `InvalidNumber`, and a
+    /// prefix without even the start of a value gives `EmptyInput`.
+    UnexpectedEof,
+    /// The input holds no value at all: it is empty, or only whitespace, or
+    /// only comments when they are allowed.
+    ///
+    /// ```
+    /// use json::ErrorKind;
+    ///
+    /// assert!(matches!(json::parse("").unwrap_err().kind, ErrorKind::EmptyInput));
+    /// let err = json::parse(" \n\t ").unwrap_err();
+    /// assert!(matches!(err.kind, ErrorKind::EmptyInput));
+    /// assert_eq!((err.line, err.column), (2, 3));
+    /// assert!(matches!(json::parse("[").unwrap_err().kind, ErrorKind::UnexpectedEof));
+    /// ```
+    EmptyInput,
+    InvalidEscape,
+    InvalidControlChar(char),
+    /// A number that breaks the JSON grammar, like a sign or a `.` without
+    /// digits after it, a leading zero or an empty exponent. The error points
+    /// at the first character that doesn't fit.
+    ///
+    /// ```
+    /// use json::ErrorKind;
+    ///
+    /// for input in ["-", "-.5", "-e1", "[-]", "01", "1.e3", "[2e+]"] {
+    ///     let err = json::parse(input).unwrap_err();
+    ///     assert!(matches!(err.kind, ErrorKind::InvalidNumber));
+    /// }
+    /// assert_eq!(json::parse("-e1").unwrap_err().column, 2);
+    /// assert_eq!(json::parse("-").unwrap_err().column, 2);
+    /// ```
+    InvalidNumber,
+    MissingComma,
+    DepthLimitExceeded,
+    UnterminatedComment,
+    TrailingData,
+    InvalidUtf8,
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+    DuplicateKey(String),
+    /// The input or a string in it is longer than the parser allows.
+    SizeLimitExceeded,
+    /// The document is a bare scalar, with `require_structured_root`.
+    RootMustBeObjectOrArray,
+    /// Something other than a string where an object key should be, as in
+    /// `{port: 8080}`.
+    ExpectedStringKey,
+}
+
+/// A parse failure along with where it happened. `line` and `column` start
+/// at 1, `byte_offset` at 0.
+#[derive(Debug)]
+pub struct ParseError {
+    pub kind: ErrorKind,
+    pub line: usize,
+    pub column: usize,
+    pub byte_offset: usize,
+}
+
+impl ParseError {
+    /// Shows the line of `input` around the error, up to 20 characters on
+    /// each side, with a caret under the character at fault. `input` has to be
+    /// the text that failed to parse.
+    ///
+    /// ```
+    /// let input = r#"{"name": "server", "host": "localhost", "port": @}"#;
+    /// let err = json::parse(input).unwrap_err();
+    /// assert_eq!(
+    ///     err.snippet(input),
+    ///     "...localhost\", \"port\": @}\n                       ^",
+    /// );
+    /// ```
+    pub fn snippet(&self, input: &str) -> String {
+        const CONTEXT: usize = 20;
+
+        let (line_start, at, line_end) = self.locate(input);
+        let start = input[line_start..at]
+            .char_indices()
+            .rev()
+            .nth(CONTEXT - 1)
+            .map_or(line_start, |(i, _)| line_start + i);
+        // the character at fault and the ones after it
+        let end = input[at..line_end]
+            .char_indices()
+            .nth(CONTEXT + 1)
+            .map_or(line_end, |(i, _)| at + i);
+
+        let mut snippet = String::new();
+        if start > line_start {
+            snippet.push_str("...");
+        }
+        let caret = snippet.len() + input[start..at].chars().count();
+        // tabs and other control characters would throw the caret off
+        snippet.extend(
+            input[start..end]
+                .chars()
+                .map(|c| if c.is_control() { ' ' } else { c }),
+        );
+        if end < line_end {
+            snippet.push_str("...");
+        }
+        snippet.push('\n');
+        snippet.extend(core::iter::repeat_n(' ', caret));
+        snippet.push('^');
+
+        snippet
+    }
+
+    /// Formats the error the way rustc does, with the whole line of `input`
+    /// it is on and a caret under the character at fault. `input` has to be
+    /// the text that failed to parse.
+    ///
+    /// ```
+    /// let input = "{\n    \"port\": @\n}";
+    /// let err = json::parse(input).unwrap_err();
+    /// let expected = "\
+    /// error: unexpected character '@'
+    ///  --> line 2, column 13
+    ///   |
+    /// 2 |     \"port\": @
+    ///   |             ^
+    /// ";
+    /// assert_eq!(err.render(input), expected);
+    /// ```
+    pub fn render(&self, input: &str) -> String {
+        let (line_start, at, line_end) = self.locate(input);
+        let line = input[line_start..line_end].trim_end_matches('\r');
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let caret = input[line_start..at].chars().count();
+
+        let mut rendered = format!(
+            "error: {}\n{}--> line {}, column {}\n{} |\n{} | ",
+            self.kind, gutter, self.line, self.column, gutter, number
+        );
+        // tabs and other control characters would throw the caret off
+        rendered.extend(line.chars().map(|c| if c.is_control() { ' ' } else { c }));
+        rendered.push('\n');
+        rendered.push_str(&gutter);
+        rendered.push_str(" | ");
+        rendered.extend(core::iter::repeat_n(' ', caret));
+        rendered.push_str("^\n");
+
+        rendered
+    }
+
+    /// Finds the error in `input`, as the byte offsets of the start of its
+    /// line, of the character at fault and of the end of the line.
+    fn locate(&self, input: &str) -> (usize, usize, usize) {
+        let mut at = self.byte_offset.min(input.len());
+        while !input.is_char_boundary(at) {
+            at -= 1;
+        }
+        let line_start = input[..at].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[at..].find('\n').map_or(input.len(), |i| at + i);
+
+        (line_start, at, line_end)
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::UnexpectedChar(c) => {
+                write!(f, "unexpected character '{}'", c.escape_debug())
+            }
+            ErrorKind::UnexpectedEof => f.write_str("unexpected end of input"),
+            ErrorKind::EmptyInput => f.write_str("empty input"),
+            ErrorKind::InvalidEscape => f.write_str("invalid escape sequence"),
+            ErrorKind::InvalidControlChar(c) => {
+                write!(
+                    f,
+                    "unescaped control character '{}' in string",
+                    c.escape_debug()
+                )
+            }
+            ErrorKind::InvalidNumber => f.write_str("invalid number"),
+            ErrorKind::MissingComma => f.write_str("missing comma"),
+            ErrorKind::DepthLimitExceeded => f.write_str("nesting depth limit exceeded"),
+            ErrorKind::UnterminatedComment => f.write_str("unterminated comment"),
+            ErrorKind::TrailingData => f.write_str("trailing data after value"),
+            ErrorKind::InvalidUtf8 => f.write_str("invalid UTF-8"),
+            #[cfg(feature = "std")]
+            ErrorKind::Io(err) => write!(f, "I/O error: {}", err),
+            ErrorKind::DuplicateKey(key) => write!(f, "duplicate key \"{}\"", key.escape_debug()),
+            ErrorKind::SizeLimitExceeded => f.write_str("size limit exceeded"),
+            ErrorKind::RootMustBeObjectOrArray => f.write_str("root must be an object or array"),
+            ErrorKind::ExpectedStringKey => f.write_str("expected a string as object key"),
+        }
+    }
+}
+
+/// Formats as e.g. `unexpected character '}' at line 3, column 12`.
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.kind, self.line, self.column
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            ErrorKind::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Why `read_json_file` failed: the file couldn't be read, or its contents
+/// weren't valid JSON.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum JsonFileError {
+    Io(std::io::Error),
+    Parse(ParseError),
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for JsonFileError {
+    fn from(err: std::io::Error) -> Self {
+        JsonFileError::Io(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<ParseError> for JsonFileError {
+    fn from(err: ParseError) -> Self {
+        JsonFileError::Parse(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for JsonFileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonFileError::Io(err) => write!(f, "failed to read file: {}", err),
+            JsonFileError::Parse(err) => write!(f, "failed to parse file: {}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for JsonFileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            JsonFileError::Io(err) => Some(err),
+            JsonFileError::Parse(err) => Some(err),
+        }
+    }
+}
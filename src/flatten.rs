@@ -0,0 +1,200 @@
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::{
+    collections::LookupMap,
+    diff::push_segment,
+    pointer::{escape, parse_index, unescape},
+    Map, Value,
+};
+
+/// Why `unflatten` couldn't rebuild a tree, with the path of the pair at
+/// fault.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnflattenError {
+    /// The path is neither empty nor starts with `/`.
+    InvalidPointer(String),
+    /// The path was given before, or goes through a value that isn't a
+    /// container, or names a key under an array.
+    Conflict(String),
+    /// The element at this path is missing while later ones were given.
+    MissingElement(String),
+}
+
+impl fmt::Display for UnflattenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UnflattenError::InvalidPointer(path) => write!(f, "invalid JSON Pointer {:?}", path),
+            UnflattenError::Conflict(path) => write!(f, "conflicting value at {:?}", path),
+            UnflattenError::MissingElement(path) => write!(f, "missing array element {:?}", path),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for UnflattenError {}
+
+impl Value {
+    /// Lists the scalars in the tree, each with the JSON Pointer leading to it,
+    /// in the order they would be written out.
+    ///
+    /// Arrays and objects aren't listed themselves, only what they contain, so
+    /// an empty one would leave no trace. With `empty_containers` they are
+    /// listed as well, as their own leaves.
+    ///
+    /// ```
+    /// let value = json::parse(r#"{"server": {"ports": [8080], "tags": []}}"#).unwrap();
+    /// let pairs = value.flatten(false);
+    /// assert_eq!(pairs, [("/server/ports/0".to_string(), &json::Value::Integer(8080))]);
+    /// assert_eq!(value.flatten(true)[1].0, "/server/tags");
+    /// ```
+    pub fn flatten(&self, empty_containers: bool) -> Vec<(String, &Value)> {
+        let mut pairs = Vec::new();
+        flatten_at(&mut String::new(), self, empty_containers, &mut pairs);
+        pairs
+    }
+}
+
+fn flatten_at<'a>(
+    path: &mut String,
+    value: &'a Value,
+    empty_containers: bool,
+    pairs: &mut Vec<(String, &'a Value)>,
+) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map.iter() {
+                let len = push_segment(path, &escape(key));
+                flatten_at(path, value, empty_containers, pairs);
+                path.truncate(len);
+            }
+        }
+        Value::Array(vec) if !vec.is_empty() => {
+            for (i, value) in vec.iter().enumerate() {
+                let len = push_segment(path, &i.to_string());
+                flatten_at(path, value, empty_containers, pairs);
+                path.truncate(len);
+            }
+        }
+        Value::Object(_) | Value::Array(_) if !empty_containers => {}
+        _ => pairs.push((path.clone(), value)),
+    }
+}
+
+/// A tree being rebuilt by `unflatten`.
+enum Node {
+    /// Nothing has been put here yet.
+    Unset,
+    Leaf(Value),
+    /// The elements given so far, by index.
+    Array(BTreeMap<usize, Node>),
+    /// The members in the order they were first given, with an index by key.
+    Object(Vec<(String, Node)>, LookupMap<String, usize>),
+}
+
+/// Rebuilds a tree from JSON Pointers and the values they lead to, the
+/// reverse of `Value::flatten`.
+///
+/// Containers are created as the paths go through them: an array when the
+/// segment below is an index like `0`, an object otherwise. Under an existing
+/// object every segment is a key. The pairs can come in any order, but every
+/// array needs all of its elements from 0 up. No pairs at all give an empty
+/// object.
+///
+/// ```
+/// use json::Value;
+///
+/// let pairs = vec![
+///     ("/server/ports/1".to_string(), Value::Integer(8443)),
+///     ("/server/ports/0".to_string(), Value::Integer(8080)),
+///     ("/server/host".to_string(), Value::String("localhost".into())),
+/// ];
+/// let value = json::unflatten(pairs).unwrap();
+/// let expected = r#"{"server": {"ports": [8080, 8443], "host": "localhost"}}"#;
+/// assert_eq!(value, json::parse(expected).unwrap());
+///
+/// let pairs = vec![
+///     ("/a".to_string(), Value::Integer(1)),
+///     ("/a/b".to_string(), Value::Integer(2)),
+/// ];
+/// assert!(json::unflatten(pairs).is_err());
+/// ```
+pub fn unflatten(
+    pairs: impl IntoIterator<Item = (String, Value)>,
+) -> Result<Value, UnflattenError> {
+    let mut root = Node::Unset;
+    for (path, value) in pairs {
+        if !path.is_empty() && !path.starts_with('/') {
+            return Err(UnflattenError::InvalidPointer(path));
+        }
+
+        let mut node = &mut root;
+        for token in path.split('/').skip(1) {
+            let key = unescape(token);
+            if let Node::Unset = node {
+                *node = match parse_index(&key) {
+                    Some(_) => Node::Array(BTreeMap::new()),
+                    None => Node::Object(Vec::new(), LookupMap::new()),
+                };
+            }
+            node = match node {
+                Node::Array(elements) => match parse_index(&key) {
+                    Some(i) => elements.entry(i).or_insert(Node::Unset),
+                    None => return Err(UnflattenError::Conflict(path)),
+                },
+                Node::Object(members, index) => {
+                    let i = *index.entry(key).or_insert_with_key(|key| {
+                        members.push((key.clone(), Node::Unset));
+                        members.len() - 1
+                    });
+                    &mut members[i].1
+                }
+                _ => return Err(UnflattenError::Conflict(path)),
+            };
+        }
+        if !matches!(node, Node::Unset) {
+            return Err(UnflattenError::Conflict(path));
+        }
+        *node = Node::Leaf(value);
+    }
+
+    match root {
+        Node::Unset => Ok(Value::Object(Map::new())),
+        root => build(root, &mut String::new()),
+    }
+}
+
+/// Turns the node found at `path` into a value.
+fn build(node: Node, path: &mut String) -> Result<Value, UnflattenError> {
+    match node {
+        Node::Unset => unreachable!("only the root can be left unset"),
+        Node::Leaf(value) => Ok(value),
+        Node::Array(elements) => {
+            let mut vec = Vec::with_capacity(elements.len());
+            for (i, node) in elements {
+                let len = push_segment(path, &vec.len().to_string());
+                if i != vec.len() {
+                    return Err(UnflattenError::MissingElement(path.clone()));
+                }
+                vec.push(build(node, path)?);
+                path.truncate(len);
+            }
+            Ok(Value::Array(vec))
+        }
+        Node::Object(members, _) => {
+            let mut map = Map::new();
+            for (key, node) in members {
+                let len = push_segment(path, &escape(&key));
+                map.insert(key, build(node, path)?);
+                path.truncate(len);
+            }
+            Ok(Value::Object(map))
+        }
+    }
+}
@@ -0,0 +1,61 @@
+#![no_std]
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "std")]
+use std::{fs::read_to_string, path::Path};
+
+mod borrowed;
+mod canonical;
+mod collections;
+mod de;
+mod diff;
+mod error;
+mod flatten;
+mod map;
+mod merge;
+mod parser;
+mod patch;
+mod pointer;
+mod query;
+#[cfg(feature = "std")]
+mod read;
+mod ser;
+#[cfg(feature = "serde")]
+mod serde;
+mod stats;
+mod value;
+
+pub use borrowed::BorrowedValue;
+pub use canonical::to_canonical_string;
+pub use de::{FromJson, TypeError};
+pub use diff::{diff, Change};
+#[cfg(feature = "std")]
+pub use error::JsonFileError;
+pub use error::{ErrorKind, ParseError};
+pub use flatten::{unflatten, UnflattenError};
+pub use map::Map;
+pub use merge::{deep_merge, merge_patch, ArrayMerge};
+#[cfg(feature = "tokio")]
+pub use parser::from_async_reader;
+#[cfg(feature = "std")]
+pub use parser::from_reader;
+pub use parser::{
+    events, parse, parse_borrowed, parse_bytes, parse_chars, parse_lines, parse_prefix,
+    parse_stream, validate, DuplicateKeys, Event, Events, Parser,
+};
+pub use patch::{apply_patch, PatchError, PatchErrorKind};
+pub use query::QueryError;
+pub use ser::{minify, serialize, to_string_pretty, Format, Serializer};
+#[cfg(feature = "std")]
+pub use ser::{to_writer, to_writer_pretty};
+pub use stats::Stats;
+pub use value::Value;
+
+/// Reads and parses the JSON file at `path`.
+#[cfg(feature = "std")]
+pub fn read_json_file(path: impl AsRef<Path>) -> Result<Value, JsonFileError> {
+    Ok(parse(&read_to_string(path)?)?)
+}
@@ -0,0 +1,181 @@
+#[cfg(feature = "sorted-keys")]
+use alloc::collections::{btree_map, BTreeMap};
+use alloc::sync::Arc;
+#[cfg(not(feature = "sorted-keys"))]
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(not(feature = "sorted-keys"))]
+use crate::collections::LookupMap;
+use crate::Value;
+
+/// The members of a JSON object, kept in insertion order.
+///
+/// Lookups by key go through a hash index so they don't need to scan the
+/// members. Keys are stored as shared `Arc<str>`, so the index doesn't copy
+/// them and the parser can reuse one allocation for a repeated key.
+///
+/// With the `sorted-keys` feature the members are kept in a `BTreeMap`
+/// instead, so they are always sorted by key, in iteration and when
+/// serialized.
+#[cfg(not(feature = "sorted-keys"))]
+#[derive(Clone, Default)]
+pub struct Map {
+    entries: Vec<(Arc<str>, Value)>,
+    index: LookupMap<Arc<str>, usize>,
+}
+
+/// The members of a JSON object, sorted by key because of the `sorted-keys`
+/// feature. Without it they are kept in insertion order.
+#[cfg(feature = "sorted-keys")]
+#[derive(Clone, Default)]
+pub struct Map {
+    entries: BTreeMap<Arc<str>, Value>,
+}
+
+impl Map {
+    pub fn new() -> Self {
+        Map::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(not(feature = "sorted-keys"))]
+impl Map {
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.index.contains_key(key)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        let i = *self.index.get(key)?;
+        Some(&self.entries[i].1)
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value> {
+        let i = *self.index.get(key)?;
+        Some(&mut self.entries[i].1)
+    }
+
+    /// Inserts a member and returns the value it replaced. A replaced member
+    /// keeps its original position.
+    pub fn insert(&mut self, key: impl Into<Arc<str>>, value: Value) -> Option<Value> {
+        let key = key.into();
+        match self.index.get(&key) {
+            Some(&i) => Some(core::mem::replace(&mut self.entries[i].1, value)),
+            None => {
+                self.index.insert(key.clone(), self.entries.len());
+                self.entries.push((key, value));
+                None
+            }
+        }
+    }
+
+    /// Removes a member, shifting the ones after it to keep their order.
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        let i = self.index.remove(key)?;
+        let (_, value) = self.entries.remove(i);
+        for index in self.index.values_mut() {
+            if *index > i {
+                *index -= 1;
+            }
+        }
+
+        Some(value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.entries.iter().map(|(k, v)| (&**k, v))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&str, &mut Value)> {
+        self.entries.iter_mut().map(|(k, v)| (&**k, v))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|(k, _)| &**k)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &Value> {
+        self.entries.iter().map(|(_, v)| v)
+    }
+}
+
+#[cfg(feature = "sorted-keys")]
+impl Map {
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.entries.get(key)
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value> {
+        self.entries.get_mut(key)
+    }
+
+    /// Inserts a member and returns the value it replaced.
+    pub fn insert(&mut self, key: impl Into<Arc<str>>, value: Value) -> Option<Value> {
+        self.entries.insert(key.into(), value)
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        self.entries.remove(key)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.entries.iter().map(|(k, v)| (&**k, v))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&str, &mut Value)> {
+        self.entries.iter_mut().map(|(k, v)| (&**k, v))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(|k| &**k)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &Value> {
+        self.entries.values()
+    }
+}
+
+#[cfg(not(feature = "sorted-keys"))]
+impl IntoIterator for Map {
+    type Item = (Arc<str>, Value);
+    type IntoIter = alloc::vec::IntoIter<(Arc<str>, Value)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+#[cfg(feature = "sorted-keys")]
+impl IntoIterator for Map {
+    type Item = (Arc<str>, Value);
+    type IntoIter = btree_map::IntoIter<Arc<str>, Value>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+/// Maps are equal when they have the same members, regardless of order.
+impl PartialEq for Map {
+    fn eq(&self, other: &Map) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl fmt::Debug for Map {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,73 @@
+use core::mem;
+
+use crate::{Map, Value};
+
+/// How `deep_merge` combines two arrays found at the same place.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArrayMerge {
+    /// Append the overlay's elements after the base's.
+    Concat,
+    /// Use the overlay's array, dropping the base's.
+    Replace,
+}
+
+/// Applies a JSON Merge Patch (RFC 7386) to `target`.
+///
+/// Objects in the patch are merged into the target recursively, with `null`
+/// members deleting the matching key. Any other patch value replaces the
+/// target outright.
+pub fn merge_patch(target: &mut Value, patch: &Value) {
+    let patch = match patch {
+        Value::Object(patch) => patch,
+        _ => {
+            *target = patch.clone();
+            return;
+        }
+    };
+
+    if !matches!(target, Value::Object(_)) {
+        *target = Value::Object(Map::new());
+    }
+    if let Value::Object(map) = target {
+        for (key, value) in patch.iter() {
+            if value.is_null() {
+                map.remove(key);
+            } else if let Some(member) = map.get_mut(key) {
+                merge_patch(member, value);
+            } else {
+                let mut member = Value::Null;
+                merge_patch(&mut member, value);
+                map.insert(key, member);
+            }
+        }
+    }
+}
+
+/// Merges `overlay` on top of `base`, for layering configuration files.
+///
+/// Members present in both objects are merged recursively, and members only
+/// in one are kept. Two arrays are combined according to `arrays`. In every
+/// other case, including a `null` overlay, the overlay value wins.
+pub fn deep_merge(base: Value, overlay: Value, arrays: ArrayMerge) -> Value {
+    match (base, overlay) {
+        (Value::Object(mut base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(member) => {
+                        let old = mem::replace(member, Value::Null);
+                        *member = deep_merge(old, value, arrays);
+                    }
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+            Value::Object(base)
+        }
+        (Value::Array(mut base), Value::Array(overlay)) if arrays == ArrayMerge::Concat => {
+            base.extend(overlay);
+            Value::Array(base)
+        }
+        (_, overlay) => overlay,
+    }
+}
@@ -0,0 +1,1523 @@
+use alloc::{borrow::Cow, string::String, sync::Arc, vec::Vec};
+use core::iter::Peekable;
+#[cfg(feature = "std")]
+use std::io::Read;
+
+#[cfg(feature = "tokio")]
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+#[cfg(feature = "std")]
+use crate::read::IoChars;
+use crate::{
+    collections::{LookupMap, LookupSet},
+    BorrowedValue, ErrorKind, Map, ParseError, Value,
+};
+
+mod events;
+
+pub use events::{events, Event, Events};
+
+/// What to do when an object contains the same key more than once.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DuplicateKeys {
+    /// Keep the last value, overwriting the earlier ones.
+    #[default]
+    Last,
+    /// Keep the first value and ignore the later ones.
+    First,
+    /// Fail with `ErrorKind::DuplicateKey`.
+    Error,
+}
+
+#[derive(Debug, Clone)]
+struct Options {
+    duplicate_keys: DuplicateKeys,
+    max_depth: usize,
+    allow_comments: bool,
+    allow_trailing_commas: bool,
+    allow_non_finite: bool,
+    max_input_bytes: Option<usize>,
+    max_string_length: Option<usize>,
+    intern_keys: bool,
+    raw_numbers: bool,
+    require_structured_root: bool,
+    allow_unquoted_keys: bool,
+    allow_single_quotes: bool,
+}
+
+/// The options of `Parser::new()`, as a constant so that free functions can
+/// borrow them for as long as they need.
+const DEFAULT_OPTIONS: Options = Options {
+    duplicate_keys: DuplicateKeys::Last,
+    max_depth: 128,
+    allow_comments: false,
+    allow_trailing_commas: false,
+    allow_non_finite: false,
+    max_input_bytes: None,
+    max_string_length: None,
+    intern_keys: false,
+    raw_numbers: false,
+    require_structured_root: false,
+    allow_unquoted_keys: false,
+    allow_single_quotes: false,
+};
+
+impl Default for Options {
+    fn default() -> Self {
+        DEFAULT_OPTIONS
+    }
+}
+
+/// A place in the input. `line` and `column` are only kept up to date when
+/// there is no source, see `Reader`.
+#[derive(Clone, Copy)]
+struct Position {
+    line: usize,
+    column: usize,
+    byte_offset: usize,
+}
+
+/// Wraps the input characters and keeps track of the current position.
+///
+/// When the input is a `&str`, `source` holds it so that strings can be
+/// borrowed from it instead of copied. Only the byte offset is tracked then,
+/// and the line and column of an error are worked out from the source when
+/// the error is built, so reading doesn't pay for counting them. Other inputs
+/// aren't kept around, so their lines and columns are counted as they go.
+struct Reader<'a, 's, I: Iterator<Item = char>> {
+    iter: Peekable<I>,
+    source: Option<&'s str>,
+    /// Collects the characters of a token when there is no source to slice
+    /// it from.
+    token: Option<String>,
+    /// Where strings that need unescaping are put together, kept from one
+    /// string to the next so its allocation is reused.
+    buf: String,
+    options: &'a Options,
+    depth: usize,
+    line: usize,
+    column: usize,
+    byte_offset: usize,
+}
+
+impl<'a, 's> Reader<'a, 's, core::str::Chars<'s>> {
+    fn from_str(input: &'s str, options: &'a Options) -> Self {
+        Reader {
+            source: Some(input),
+            ..Reader::new(input.chars(), options)
+        }
+    }
+}
+
+impl<'a, 's, I: Iterator<Item = char>> Reader<'a, 's, I> {
+    fn new(iter: I, options: &'a Options) -> Self {
+        Reader {
+            iter: iter.peekable(),
+            source: None,
+            token: None,
+            buf: String::new(),
+            options,
+            depth: 0,
+            line: 1,
+            column: 1,
+            byte_offset: 0,
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.iter.peek().cloned()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.iter.next()?;
+        if let Some(token) = &mut self.token {
+            token.push(c);
+        }
+        self.byte_offset += c.len_utf8();
+        if self.source.is_none() {
+            if c == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+
+        Some(c)
+    }
+
+    /// The source text between two byte offsets, if the input is a `&str`.
+    fn slice(&self, start: usize, end: usize) -> Option<&'s str> {
+        self.source.map(|source| &source[start..end])
+    }
+
+    /// Starts a token at the next character, returning its offset for
+    /// `end_token`.
+    fn begin_token(&mut self) -> usize {
+        if self.source.is_none() {
+            self.token = Some(String::new());
+        }
+        self.byte_offset
+    }
+
+    /// The text consumed since `begin_token`.
+    fn end_token(&mut self, start: usize) -> Cow<'s, str> {
+        match self.token.take() {
+            Some(token) => Cow::Owned(token),
+            None => Cow::Borrowed(self.slice(start, self.byte_offset).unwrap_or_default()),
+        }
+    }
+
+    /// Called when entering an array or object, fails if that goes over the
+    /// depth limit.
+    fn enter(&mut self) -> Result<(), ParseError> {
+        if self.depth >= self.options.max_depth {
+            return Err(self.error(ErrorKind::DepthLimitExceeded));
+        }
+        self.depth += 1;
+
+        Ok(())
+    }
+
+    fn leave(&mut self) {
+        self.depth -= 1;
+    }
+
+    fn position(&self) -> Position {
+        Position {
+            line: self.line,
+            column: self.column,
+            byte_offset: self.byte_offset,
+        }
+    }
+
+    /// Builds an error located at the next unread character.
+    fn error(&self, kind: ErrorKind) -> ParseError {
+        self.error_at(kind, self.position())
+    }
+
+    fn error_at(&self, kind: ErrorKind, at: Position) -> ParseError {
+        let (line, column) = match self.source {
+            Some(source) => line_and_column(&source[..at.byte_offset]),
+            None => (at.line, at.column),
+        };
+
+        ParseError {
+            kind,
+            line,
+            column,
+            byte_offset: at.byte_offset,
+        }
+    }
+}
+
+/// The line and column right after `text`, both starting at 1.
+fn line_and_column(text: &str) -> (usize, usize) {
+    let line_start = text.rfind('\n').map_or(0, |i| i + 1);
+    let line = text[..line_start].matches('\n').count() + 1;
+
+    (line, text[line_start..].chars().count() + 1)
+}
+
+/// A JSON parser with configurable behavior. `Parser::new()` behaves the same
+/// as the free `parse` functions.
+///
+/// A parser can also keep its scratch buffers between documents, see
+/// `parse_reusing`.
+#[derive(Debug, Clone, Default)]
+pub struct Parser {
+    options: Options,
+    scratch: Scratch,
+}
+
+/// Buffers that `Parser::parse_reusing` clears instead of dropping, so parsing
+/// another document doesn't have to allocate them again.
+#[derive(Debug, Clone, Default)]
+struct Scratch {
+    string: String,
+    keys: LookupSet<Arc<str>>,
+}
+
+impl Parser {
+    pub fn new() -> Self {
+        Parser::default()
+    }
+
+    /// Sets how many arrays and objects may be nested inside each other,
+    /// 128 by default.
+    ///
+    /// Parsing itself doesn't recurse, but dropping, printing or comparing a
+    /// `Value` does, so a limit far above the default needs a large enough
+    /// stack for whatever is done with the result. `Value::drop_deep` frees
+    /// one without recursing.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.options.max_depth = max_depth;
+        self
+    }
+
+    /// Sets what happens to repeated object keys, `DuplicateKeys::Last` by
+    /// default.
+    pub fn duplicate_keys(mut self, policy: DuplicateKeys) -> Self {
+        self.options.duplicate_keys = policy;
+        self
+    }
+
+    /// Allows `// line` and `/* block */` comments wherever whitespace is
+    /// allowed. Off by default, since they aren't part of JSON.
+    pub fn allow_comments(mut self, allow: bool) -> Self {
+        self.options.allow_comments = allow;
+        self
+    }
+
+    /// Allows a single comma after the last element of an array or object,
+    /// as in `[1, 2,]`. Off by default.
+    pub fn allow_trailing_commas(mut self, allow: bool) -> Self {
+        self.options.allow_trailing_commas = allow;
+        self
+    }
+
+    /// Accepts the bare tokens `NaN`, `Infinity` and `-Infinity` as numbers,
+    /// like some JSON producers emit. Off by default.
+    ///
+    /// These values can't be written back as JSON: the serializer outputs
+    /// `null` for them.
+    pub fn allow_non_finite(mut self, allow: bool) -> Self {
+        self.options.allow_non_finite = allow;
+        self
+    }
+
+    /// Rejects inputs longer than `max` bytes with
+    /// `ErrorKind::SizeLimitExceeded`, checked before parsing starts. Streams
+    /// are cut off once they go over. Unlimited by default.
+    pub fn max_input_bytes(mut self, max: usize) -> Self {
+        self.options.max_input_bytes = Some(max);
+        self
+    }
+
+    /// Rejects string literals, keys included, longer than `max` bytes as
+    /// written in the input, with `ErrorKind::SizeLimitExceeded`. Unlimited by
+    /// default.
+    pub fn max_string_length(mut self, max: usize) -> Self {
+        self.options.max_string_length = Some(max);
+        self
+    }
+
+    /// Makes all the objects of a document share one allocation per distinct
+    /// key, which saves memory on arrays of similar records. Off by default,
+    /// since it costs a lookup for every key.
+    pub fn intern_keys(mut self, intern: bool) -> Self {
+        self.options.intern_keys = intern;
+        self
+    }
+
+    /// Keeps numbers as `Value::RawNumber`, the text exactly as written, so
+    /// that `1.0`, `1E3` or `100.00` are written back the same and no
+    /// precision is lost. Off by default.
+    pub fn raw_numbers(mut self, raw: bool) -> Self {
+        self.options.raw_numbers = raw;
+        self
+    }
+
+    /// Rejects documents that are a bare string, number, boolean or `null`
+    /// with `ErrorKind::RootMustBeObjectOrArray`, as some APIs require. Off by
+    /// default, since JSON allows any value at the root.
+    pub fn require_structured_root(mut self, require: bool) -> Self {
+        self.options.require_structured_root = require;
+        self
+    }
+
+    /// Accepts object keys written as bare identifiers, as in JSON5: a letter,
+    /// `_` or `$`, then any number of letters, digits, `_` or `$`. Only ASCII
+    /// letters count. Off by default.
+    ///
+    /// ```
+    /// let parser = json::Parser::new().allow_unquoted_keys(true);
+    /// let value = parser.parse("{port: 8080, $tag_1: true}").unwrap();
+    /// assert_eq!(value["port"], json::Value::Integer(8080));
+    /// assert!(parser.parse("{1port: 8080}").is_err());
+    /// ```
+    pub fn allow_unquoted_keys(mut self, allow: bool) -> Self {
+        self.options.allow_unquoted_keys = allow;
+        self
+    }
+
+    /// Accepts strings and keys in single quotes, as in JSON5:
+    /// `{'name': 'value'}`. They have the same escapes as double-quoted
+    /// strings, plus `\'`, and `"` stands for itself inside them. Off by
+    /// default.
+    pub fn allow_single_quotes(mut self, allow: bool) -> Self {
+        self.options.allow_single_quotes = allow;
+        self
+    }
+
+    /// Parses a single JSON document, rejecting anything but whitespace after
+    /// it.
+    pub fn parse(&self, input: &str) -> Result<Value, ParseError> {
+        check_input_size(&self.options, input.len())?;
+        let mut build = OwnedTree::new(&self.options);
+        parse_document(&mut Reader::from_str(input, &self.options), &mut build)
+    }
+
+    /// Parses a single JSON document like `parse`, but keeps the buffers used
+    /// along the way for the next call instead of freeing them.
+    ///
+    /// This saves allocations when parsing many small documents in a loop,
+    /// e.g. one per request. What is kept is the buffer for unescaping strings
+    /// and, with `intern_keys`, the capacity of the set of keys. The interned
+    /// keys themselves are still only shared within one document.
+    pub fn parse_reusing(&mut self, input: &str) -> Result<Value, ParseError> {
+        check_input_size(&self.options, input.len())?;
+        let scratch = &mut self.scratch;
+        let mut iter = Reader::from_str(input, &self.options);
+        iter.buf = core::mem::take(&mut scratch.string);
+        let mut build = OwnedTree {
+            keys: if self.options.intern_keys {
+                Some(core::mem::take(&mut scratch.keys))
+            } else {
+                None
+            },
+        };
+
+        let result = parse_document(&mut iter, &mut build);
+        scratch.string = iter.buf;
+        if let Some(mut keys) = build.keys {
+            keys.clear();
+            scratch.keys = keys;
+        }
+        result
+    }
+
+    /// Checks that `input` is a single valid JSON document under this parser's
+    /// options, without building it. See `json::validate`.
+    pub fn validate(&self, input: &str) -> Result<(), ParseError> {
+        check_input_size(&self.options, input.len())?;
+        let mut build = Validate {
+            track_keys: self.options.duplicate_keys == DuplicateKeys::Error,
+        };
+        parse_document(&mut Reader::from_str(input, &self.options), &mut build)
+    }
+
+    /// Parses a single JSON document into a `BorrowedValue`, which refers to
+    /// `input` for every string that has no escape sequences.
+    pub fn parse_borrowed<'s>(&self, input: &'s str) -> Result<BorrowedValue<'s>, ParseError> {
+        check_input_size(&self.options, input.len())?;
+        parse_document(
+            &mut Reader::from_str(input, &self.options),
+            &mut BorrowedTree,
+        )
+    }
+
+    /// Parses one value from the start of `input` and returns it with the rest
+    /// of the input, which is left unchecked. Whitespace right after the value
+    /// is skipped.
+    pub fn parse_prefix<'s>(&self, input: &'s str) -> Result<(Value, &'s str), ParseError> {
+        check_input_size(&self.options, input.len())?;
+        let mut iter = Reader::from_str(input, &self.options);
+        skip_bom(&mut iter);
+        let value = parse_element(&mut iter, &mut OwnedTree::new(&self.options))?;
+
+        Ok((value, &input[iter.byte_offset..]))
+    }
+
+    /// Parses a single JSON document from UTF-8 encoded bytes.
+    pub fn parse_bytes(&self, input: &[u8]) -> Result<Value, ParseError> {
+        check_input_size(&self.options, input.len())?;
+        match core::str::from_utf8(input) {
+            Ok(s) => self.parse(s),
+            Err(err) => {
+                let prefix = core::str::from_utf8(&input[..err.valid_up_to()]).unwrap_or_default();
+                let (line, column) = line_and_column(prefix);
+                Err(ParseError {
+                    // the input may have been cut in the middle of a character
+                    kind: match err.error_len() {
+                        Some(_) => ErrorKind::InvalidUtf8,
+                        None => ErrorKind::UnexpectedEof,
+                    },
+                    line,
+                    column,
+                    byte_offset: prefix.len(),
+                })
+            }
+        }
+    }
+
+    /// Parses a single JSON document from any source of characters, reading
+    /// them one at a time, e.g. from a decoder. `max_input_bytes` counts the
+    /// characters as UTF-8.
+    ///
+    /// ```
+    /// let utf16: Vec<u16> = "{\"name\": \"caf\u{e9}\"}".encode_utf16().collect();
+    /// let chars = char::decode_utf16(utf16).map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER));
+    /// let value = json::Parser::new().parse_chars(chars).unwrap();
+    /// assert_eq!(value["name"].as_str(), Some("caf\u{e9}"));
+    /// ```
+    pub fn parse_chars<I: IntoIterator<Item = char>>(&self, chars: I) -> Result<Value, ParseError> {
+        let mut chars = Limited {
+            iter: chars.into_iter(),
+            left: self.options.max_input_bytes,
+            exceeded: false,
+        };
+        let mut iter = Reader::new(&mut chars, &self.options);
+
+        let result = parse_document(&mut iter, &mut OwnedTree::new(&self.options));
+        // as with `parse_reader`, an error after the cut-off comes from it
+        let eof = iter.error(ErrorKind::UnexpectedEof);
+        if chars.exceeded {
+            return Err(ParseError {
+                kind: ErrorKind::SizeLimitExceeded,
+                ..eof
+            });
+        }
+
+        result
+    }
+
+    /// Parses a single JSON document from a byte stream, decoding UTF-8 as it
+    /// goes instead of reading the whole input up front.
+    #[cfg(feature = "std")]
+    pub fn parse_reader<R: Read>(&self, reader: R) -> Result<Value, ParseError> {
+        let mut chars = IoChars::new(reader, self.options.max_input_bytes);
+        let mut iter = Reader::new(&mut chars, &self.options);
+
+        let result = parse_document(&mut iter, &mut OwnedTree::new(&self.options));
+        // the reader gives up at the first bad byte, so whatever went wrong
+        // while parsing is a consequence of the stream error
+        let eof = iter.error(ErrorKind::UnexpectedEof);
+        match chars.error.take() {
+            Some(kind) => Err(ParseError { kind, ..eof }),
+            None => result,
+        }
+    }
+
+    /// Reads a whole document from an async byte stream, then parses it like
+    /// `parse_bytes`. Reading stops once the input goes over
+    /// `max_input_bytes`.
+    #[cfg(feature = "tokio")]
+    pub async fn parse_async_reader<R: AsyncRead + Unpin>(
+        &self,
+        reader: R,
+    ) -> Result<Value, ParseError> {
+        // one byte past the limit is enough to tell that the input is over it
+        let limit = self
+            .options
+            .max_input_bytes
+            .map_or(u64::MAX, |max| max as u64 + 1);
+        let mut input = Vec::new();
+        if let Err(err) = reader.take(limit).read_to_end(&mut input).await {
+            return Err(ParseError {
+                kind: ErrorKind::Io(err),
+                line: 1,
+                column: 1,
+                byte_offset: 0,
+            });
+        }
+
+        self.parse_bytes(&input)
+    }
+}
+
+/// Parses a single JSON document, rejecting anything but whitespace after it.
+///
+/// Whitespace is only what JSON allows between tokens: spaces, tabs, line
+/// feeds and carriage returns.
+///
+/// ```
+/// assert!(json::parse(" [1,\t2]\r\n").is_ok());
+/// assert!(json::parse("[1,\u{a0}2]").is_err());
+/// assert!(json::parse("[1]\u{c}").is_err());
+/// ```
+pub fn parse(input: &str) -> Result<Value, ParseError> {
+    Parser::new().parse(input)
+}
+
+/// Parses a single JSON document, borrowing strings from `input` where
+/// possible. See `BorrowedValue`.
+pub fn parse_borrowed(input: &str) -> Result<BorrowedValue<'_>, ParseError> {
+    Parser::new().parse_borrowed(input)
+}
+
+/// Checks that `input` is a single valid JSON document, failing with the same
+/// error as `parse` would, but without building the value.
+///
+/// Nothing is allocated apart from the stack of open arrays and objects, and
+/// strings with escape sequences.
+pub fn validate(input: &str) -> Result<(), ParseError> {
+    Parser::new().validate(input)
+}
+
+/// Parses one value from the start of `input` and returns it with the rest
+/// of the input, which is left unchecked. Whitespace right after the value is
+/// skipped.
+pub fn parse_prefix(input: &str) -> Result<(Value, &str), ParseError> {
+    Parser::new().parse_prefix(input)
+}
+
+/// Parses a stream of JSON values written one after the other, like
+/// `{"a":1}{"b":2}[1,2,3]`, with optional whitespace in between. A byte order
+/// mark is only skipped at the start of `input`.
+///
+/// Iteration stops after the first error, since there is no telling where the
+/// next value would start.
+pub fn parse_stream(input: &str) -> impl Iterator<Item = Result<Value, ParseError>> + '_ {
+    let mut rest = input;
+    let mut failed = false;
+    core::iter::from_fn(move || {
+        if failed || rest.trim_start_matches(is_whitespace).is_empty() {
+            return None;
+        }
+
+        let mut iter = Reader::from_str(rest, &DEFAULT_OPTIONS);
+        // past the first value, a byte order mark is just a stray character
+        if rest.len() == input.len() {
+            skip_bom(&mut iter);
+        }
+        match parse_element(&mut iter, &mut OwnedTree::new(&DEFAULT_OPTIONS)) {
+            Ok(value) => {
+                rest = &rest[iter.byte_offset..];
+                Some(Ok(value))
+            }
+            Err(err) => {
+                failed = true;
+                let consumed = &input[..input.len() - rest.len()];
+                Some(Err(relocate(err, consumed)))
+            }
+        }
+    })
+}
+
+/// Moves the position of an error found in the input following `consumed` so
+/// that it is relative to the start of `consumed` instead.
+fn relocate(err: ParseError, consumed: &str) -> ParseError {
+    let (line, column) = line_and_column(consumed);
+    let column = match err.line {
+        1 => column - 1 + err.column,
+        _ => err.column,
+    };
+
+    ParseError {
+        line: line - 1 + err.line,
+        column,
+        byte_offset: consumed.len() + err.byte_offset,
+        ..err
+    }
+}
+
+/// Parses newline-delimited JSON (JSON Lines), one document per line. Blank
+/// lines are skipped, and a byte order mark is only skipped at the start of
+/// `input`.
+///
+/// Error positions are relative to the whole input, not to the line.
+pub fn parse_lines(input: &str) -> impl Iterator<Item = Result<Value, ParseError>> + '_ {
+    let mut offset = 0;
+    input.split('\n').enumerate().filter_map(move |(i, line)| {
+        let start = offset;
+        offset += line.len() + 1;
+        if line.trim_matches(is_whitespace).is_empty() {
+            return None;
+        }
+
+        let mut iter = Reader::from_str(line, &DEFAULT_OPTIONS);
+        if start == 0 {
+            skip_bom(&mut iter);
+        }
+        let value = parse_to_end(&mut iter, &mut OwnedTree::new(&DEFAULT_OPTIONS));
+        Some(value.map_err(|err| ParseError {
+            line: i + 1,
+            byte_offset: start + err.byte_offset,
+            ..err
+        }))
+    })
+}
+
+/// Parses a single JSON document from UTF-8 encoded bytes.
+pub fn parse_bytes(input: &[u8]) -> Result<Value, ParseError> {
+    Parser::new().parse_bytes(input)
+}
+
+/// Parses a single JSON document from any source of characters. See
+/// `Parser::parse_chars`.
+pub fn parse_chars<I: IntoIterator<Item = char>>(chars: I) -> Result<Value, ParseError> {
+    Parser::new().parse_chars(chars)
+}
+
+/// Parses a single JSON document from a byte stream, decoding UTF-8 as it
+/// goes instead of reading the whole input up front.
+#[cfg(feature = "std")]
+pub fn from_reader<R: Read>(reader: R) -> Result<Value, ParseError> {
+    Parser::new().parse_reader(reader)
+}
+
+/// Reads a whole document from an async byte stream, then parses it. Use
+/// `Parser::parse_async_reader` with `max_input_bytes` to cap how much is
+/// read from an untrusted stream.
+#[cfg(feature = "tokio")]
+pub async fn from_async_reader<R: AsyncRead + Unpin>(reader: R) -> Result<Value, ParseError> {
+    Parser::new().parse_async_reader(reader).await
+}
+
+/// Ends a stream of characters before it goes over `left` bytes of UTF-8,
+/// recording that it did.
+struct Limited<I> {
+    iter: I,
+    left: Option<usize>,
+    exceeded: bool,
+}
+
+impl<I: Iterator<Item = char>> Iterator for Limited<I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.iter.next()?;
+        if let Some(left) = &mut self.left {
+            match left.checked_sub(c.len_utf8()) {
+                Some(rest) => *left = rest,
+                None => {
+                    self.exceeded = true;
+                    return None;
+                }
+            }
+        }
+
+        Some(c)
+    }
+}
+
+/// Fails up front when the input is over `max_input_bytes`.
+fn check_input_size(options: &Options, len: usize) -> Result<(), ParseError> {
+    match options.max_input_bytes {
+        Some(max) if len > max => Err(ParseError {
+            kind: ErrorKind::SizeLimitExceeded,
+            line: 1,
+            column: 1,
+            byte_offset: 0,
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn parse_document<'s, I: Iterator<Item = char>, B: Build<'s>>(
+    iter: &mut Reader<'_, 's, I>,
+    build: &mut B,
+) -> Result<B::Value, ParseError> {
+    skip_bom(iter);
+    parse_to_end(iter, build)
+}
+
+/// Parses a value with nothing but whitespace after it.
+fn parse_to_end<'s, I: Iterator<Item = char>, B: Build<'s>>(
+    iter: &mut Reader<'_, 's, I>,
+    build: &mut B,
+) -> Result<B::Value, ParseError> {
+    let value = parse_element(iter, build)?;
+    if iter.peek().is_some() {
+        build.discard(value);
+        return Err(iter.error(ErrorKind::TrailingData));
+    }
+
+    Ok(value)
+}
+
+fn skip_bom<I: Iterator<Item = char>>(iter: &mut Reader<I>) {
+    // a byte order mark is only allowed as the very first character
+    if iter.peek() == Some('\u{FEFF}') {
+        iter.next();
+    }
+}
+
+/// Parses a value without recursing: arrays and objects that are still open
+/// live on an explicit stack, so deep nesting only costs heap memory.
+fn parse_element<'s, I: Iterator<Item = char>, B: Build<'s>>(
+    iter: &mut Reader<'_, 's, I>,
+    build: &mut B,
+) -> Result<B::Value, ParseError> {
+    let mut stack = Vec::new();
+    let result = parse_nested(iter, build, &mut stack);
+    // on failure, what was parsed of the containers left open is freed one
+    // container at a time
+    for frame in stack.into_iter().rev() {
+        let value = frame.into_value(build);
+        build.discard(value);
+    }
+
+    result
+}
+
+fn parse_nested<'s, I: Iterator<Item = char>, B: Build<'s>>(
+    iter: &mut Reader<'_, 's, I>,
+    build: &mut B,
+    stack: &mut Vec<Frame<'s, B>>,
+) -> Result<B::Value, ParseError> {
+    check_root(iter)?;
+
+    'value: loop {
+        skip_whitespace(iter)?;
+        let mut value = match iter.peek() {
+            Some('[') => {
+                iter.enter()?;
+                iter.next();
+                skip_whitespace(iter)?;
+                if iter.peek() == Some(']') {
+                    iter.next();
+                    iter.leave();
+                    build.array(Vec::new())
+                } else {
+                    stack.push(Frame::Array(Vec::new()));
+                    continue;
+                }
+            }
+            Some('{') => {
+                iter.enter()?;
+                iter.next();
+                skip_whitespace(iter)?;
+                if iter.peek() == Some('}') {
+                    iter.next();
+                    iter.leave();
+                    let object = build.new_object();
+                    build.object(object)
+                } else {
+                    let (key, key_at) = parse_key(iter)?;
+                    stack.push(Frame::Object(build.new_object(), key, key_at));
+                    continue;
+                }
+            }
+            _ => parse_scalar(iter, build)?,
+        };
+
+        // hand the finished value to its parent, closing every container
+        // that ends right after it
+        loop {
+            skip_whitespace(iter)?;
+            let mut frame = match stack.pop() {
+                Some(frame) => frame,
+                None => return Ok(value),
+            };
+            if !frame.push(iter, build, value)? {
+                stack.push(frame);
+                continue 'value;
+            }
+            iter.leave();
+            value = frame.into_value(build);
+        }
+    }
+}
+
+/// Fails when there is no root value at all, and on a scalar at the root with
+/// `require_structured_root`. Called before the root value is read.
+fn check_root<I: Iterator<Item = char>>(iter: &mut Reader<I>) -> Result<(), ParseError> {
+    skip_whitespace(iter)?;
+    if iter.peek().is_none() {
+        return Err(iter.error(ErrorKind::EmptyInput));
+    }
+    if iter.options.require_structured_root {
+        let scalar = match iter.peek() {
+            Some('[') | Some('{') => false,
+            Some('N') | Some('I') => iter.options.allow_non_finite,
+            Some('\'') => iter.options.allow_single_quotes,
+            // characters that don't start any value get the usual error
+            Some(c) => starts_value(c),
+            None => false,
+        };
+        if scalar {
+            return Err(iter.error(ErrorKind::RootMustBeObjectOrArray));
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_scalar<'s, I: Iterator<Item = char>, B: Build<'s>>(
+    iter: &mut Reader<'_, 's, I>,
+    build: &mut B,
+) -> Result<B::Value, ParseError> {
+    match iter.peek() {
+        Some('"') => Ok(build.string(parse_string(iter)?)),
+        Some('\'') if iter.options.allow_single_quotes => Ok(build.string(parse_string(iter)?)),
+        Some(c) if c.is_ascii_digit() || c == '-' => parse_number(iter, build),
+        Some('t') => parse_literal(iter, "true").map(|_| build.bool(true)),
+        Some('f') => parse_literal(iter, "false").map(|_| build.bool(false)),
+        Some('n') => parse_literal(iter, "null").map(|_| build.null()),
+        Some('N') if iter.options.allow_non_finite => {
+            parse_literal(iter, "NaN").map(|_| build.number(f64::NAN))
+        }
+        Some('I') if iter.options.allow_non_finite => {
+            parse_literal(iter, "Infinity").map(|_| build.number(f64::INFINITY))
+        }
+        Some(c) => Err(iter.error(ErrorKind::UnexpectedChar(c))),
+        None => Err(iter.error(ErrorKind::UnexpectedEof)),
+    }
+}
+
+fn expect_char<I: Iterator<Item = char>>(
+    iter: &mut Reader<I>,
+    expected: char,
+) -> Result<(), ParseError> {
+    match iter.peek() {
+        Some(c) if c == expected => {
+            iter.next();
+            Ok(())
+        }
+        Some(c) => Err(iter.error(ErrorKind::UnexpectedChar(c))),
+        None => Err(iter.error(ErrorKind::UnexpectedEof)),
+    }
+}
+
+/// Parses a string, borrowing it from the source unless it contains escapes
+/// or there is no source to borrow from.
+fn parse_string<'s, I: Iterator<Item = char>>(
+    iter: &mut Reader<'_, 's, I>,
+) -> Result<Cow<'s, str>, ParseError> {
+    let string_at = iter.position();
+    let quote = match iter.peek() {
+        Some('\'') if iter.options.allow_single_quotes => '\'',
+        _ => '"',
+    };
+    expect_char(iter, quote)?;
+    let start = iter.byte_offset;
+    // without a source every string is put together in `buf`
+    let mut owned = iter.source.is_none();
+    iter.buf.clear();
+    while let Some(c) = iter.peek() {
+        // control characters have to be escaped
+        if c < '\u{20}' {
+            return Err(iter.error(ErrorKind::InvalidControlChar(c)));
+        }
+        let end = iter.byte_offset;
+        if matches!(iter.options.max_string_length, Some(max) if end - start > max) {
+            return Err(iter.error_at(ErrorKind::SizeLimitExceeded, string_at));
+        }
+        iter.next();
+
+        if c == quote {
+            return Ok(if owned {
+                Cow::Owned(iter.buf.as_str().into())
+            } else {
+                Cow::Borrowed(iter.slice(start, end).unwrap_or_default())
+            });
+        } else if c == '\\' {
+            if !owned {
+                // the text so far had no escapes, so it can be copied as is
+                let text = iter.slice(start, end).unwrap_or_default();
+                iter.buf.push_str(text);
+                owned = true;
+            }
+            let c = parse_escape(iter, quote)?;
+            iter.buf.push(c);
+        } else if owned {
+            iter.buf.push(c);
+        }
+    }
+    Err(iter.error(ErrorKind::UnexpectedEof))
+}
+
+/// Parses what follows a backslash in a string delimited by `quote`.
+fn parse_escape<I: Iterator<Item = char>>(
+    iter: &mut Reader<I>,
+    quote: char,
+) -> Result<char, ParseError> {
+    let c = match iter.peek() {
+        Some('"') => '"',
+        Some('\'') if quote == '\'' => '\'',
+        Some('\\') => '\\',
+        Some('/') => '/',
+        Some('b') => '\x08',
+        Some('f') => '\x0C',
+        Some('n') => '\n',
+        Some('r') => '\r',
+        Some('t') => '\t',
+        Some('u') => {
+            iter.next();
+            return parse_unicode_escape(iter);
+        }
+        Some(_) => return Err(iter.error(ErrorKind::InvalidEscape)),
+        None => return Err(iter.error(ErrorKind::UnexpectedEof)),
+    };
+    iter.next();
+
+    Ok(c)
+}
+
+fn parse_unicode_escape<I: Iterator<Item = char>>(
+    iter: &mut Reader<I>,
+) -> Result<char, ParseError> {
+    let code = parse_hex4(iter)?;
+
+    match code {
+        0xD800..=0xDBFF => {
+            // high surrogate, must be followed by a low surrogate
+            expect_escape(iter, '\\')?;
+            expect_escape(iter, 'u')?;
+            let low = parse_hex4(iter)?;
+            if !(0xDC00..=0xDFFF).contains(&low) {
+                return Err(iter.error(ErrorKind::InvalidEscape));
+            }
+            core::char::from_u32(0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00))
+                .ok_or_else(|| iter.error(ErrorKind::InvalidEscape))
+        }
+        // lone low surrogates are rejected by from_u32
+        _ => core::char::from_u32(code).ok_or_else(|| iter.error(ErrorKind::InvalidEscape)),
+    }
+}
+
+/// Consumes `expected`, the next character of an escape sequence.
+fn expect_escape<I: Iterator<Item = char>>(
+    iter: &mut Reader<I>,
+    expected: char,
+) -> Result<(), ParseError> {
+    match iter.peek() {
+        Some(c) if c == expected => {
+            iter.next();
+            Ok(())
+        }
+        Some(_) => Err(iter.error(ErrorKind::InvalidEscape)),
+        None => Err(iter.error(ErrorKind::UnexpectedEof)),
+    }
+}
+
+fn parse_hex4<I: Iterator<Item = char>>(iter: &mut Reader<I>) -> Result<u32, ParseError> {
+    let mut code = 0;
+    for _ in 0..4 {
+        match iter.peek().and_then(|c| c.to_digit(16)) {
+            Some(digit) => code = code * 16 + digit,
+            None if iter.peek().is_some() => return Err(iter.error(ErrorKind::InvalidEscape)),
+            None => return Err(iter.error(ErrorKind::UnexpectedEof)),
+        }
+        iter.next();
+    }
+
+    Ok(code)
+}
+
+fn parse_number<'s, I: Iterator<Item = char>, B: Build<'s>>(
+    iter: &mut Reader<'_, 's, I>,
+    build: &mut B,
+) -> Result<B::Value, ParseError> {
+    let start = iter.begin_token();
+    if iter.peek() == Some('-') {
+        iter.next();
+        if iter.peek() == Some('I') && iter.options.allow_non_finite {
+            iter.end_token(start);
+            parse_literal(iter, "Infinity")?;
+            return Ok(build.number(f64::NEG_INFINITY));
+        }
+        // a sign on its own is no number, even at the end of the input
+        if iter.peek().is_none() {
+            return Err(iter.error(ErrorKind::InvalidNumber));
+        }
+    }
+
+    // scan to the end of the number first, then convert all of it at once
+    let scanned = scan_number(iter);
+    let token = iter.end_token(start);
+    let (fraction, exponent) = scanned?;
+    if iter.options.raw_numbers {
+        return Ok(build.raw_number(token));
+    }
+
+    // keep integers exact when they fit, "-0" stays a float to keep its sign
+    if !fraction && !exponent && token != "-0" {
+        if let Ok(int) = token.parse() {
+            return Ok(build.integer(int));
+        }
+    }
+
+    // the token follows the JSON number grammar, which f64 parsing accepts
+    // and rounds correctly
+    token
+        .parse()
+        .map(|n| build.number(n))
+        .map_err(|_| iter.error(ErrorKind::InvalidNumber))
+}
+
+/// Consumes the digits, fraction and exponent of a number, after its sign.
+/// Returns whether there was a fraction and an exponent.
+fn scan_number<I: Iterator<Item = char>>(iter: &mut Reader<I>) -> Result<(bool, bool), ParseError> {
+    if iter.peek() == Some('0') {
+        // a leading zero must be the only digit of the integer part
+        iter.next();
+        if matches!(iter.peek(), Some(c) if c.is_ascii_digit()) {
+            return Err(iter.error(ErrorKind::InvalidNumber));
+        }
+    } else {
+        scan_digits(iter)?;
+    }
+    let fraction = scan_fraction(iter)?;
+    let exponent = scan_exponent(iter)?;
+
+    Ok((fraction, exponent))
+}
+
+fn scan_fraction<I: Iterator<Item = char>>(iter: &mut Reader<I>) -> Result<bool, ParseError> {
+    if iter.peek() != Some('.') {
+        return Ok(false);
+    }
+    iter.next();
+    scan_digits(iter)?;
+
+    Ok(true)
+}
+
+fn scan_digits<I: Iterator<Item = char>>(iter: &mut Reader<I>) -> Result<(), ParseError> {
+    match iter.peek() {
+        Some(c) if c.is_ascii_digit() => {}
+        Some(_) => return Err(iter.error(ErrorKind::InvalidNumber)),
+        None => return Err(iter.error(ErrorKind::UnexpectedEof)),
+    }
+
+    while let Some(c) = iter.peek() {
+        if c.is_ascii_digit() {
+            iter.next();
+        } else {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+fn scan_exponent<I: Iterator<Item = char>>(iter: &mut Reader<I>) -> Result<bool, ParseError> {
+    if !matches!(iter.peek(), Some('e') | Some('E')) {
+        return Ok(false);
+    }
+    iter.next();
+    if matches!(iter.peek(), Some('+') | Some('-')) {
+        iter.next();
+    }
+    scan_digits(iter)?;
+
+    Ok(true)
+}
+
+/// An array or object whose closing bracket hasn't been reached yet.
+enum Frame<'s, B: Build<'s>> {
+    Array(Vec<B::Value>),
+    /// The members so far, and the key (with its position) of the member
+    /// whose value is being parsed.
+    Object(B::Object, Cow<'s, str>, Position),
+}
+
+impl<'s, B: Build<'s>> Frame<'s, B> {
+    /// Adds an element or member value, then consumes the comma or closing
+    /// bracket after it. Returns true once the container is closed.
+    fn push<I: Iterator<Item = char>>(
+        &mut self,
+        iter: &mut Reader<'_, 's, I>,
+        build: &mut B,
+        value: B::Value,
+    ) -> Result<bool, ParseError> {
+        match self {
+            Frame::Array(vec) => {
+                vec.push(value);
+                end_of_element(iter, ']')
+            }
+            Frame::Object(object, key, key_at) => {
+                insert_member(iter, build, object, core::mem::take(key), value, *key_at)?;
+                if end_of_element(iter, '}')? {
+                    return Ok(true);
+                }
+                let (next_key, next_at) = parse_key(iter)?;
+                *key = next_key;
+                *key_at = next_at;
+                Ok(false)
+            }
+        }
+    }
+
+    fn into_value(self, build: &mut B) -> B::Value {
+        match self {
+            Frame::Array(vec) => build.array(vec),
+            Frame::Object(object, _, _) => build.object(object),
+        }
+    }
+}
+
+/// Consumes what follows an element: a comma, which is only valid between two
+/// elements unless trailing commas are allowed, or the closing bracket.
+fn end_of_element<I: Iterator<Item = char>>(
+    iter: &mut Reader<I>,
+    close: char,
+) -> Result<bool, ParseError> {
+    match iter.peek() {
+        Some(',') => {
+            iter.next();
+            if iter.options.allow_trailing_commas {
+                skip_whitespace(iter)?;
+                if iter.peek() == Some(close) {
+                    iter.next();
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        }
+        Some(c) if c == close => {
+            iter.next();
+            Ok(true)
+        }
+        Some(c) if starts_value(c) || (c == '\'' && iter.options.allow_single_quotes) => {
+            Err(iter.error(ErrorKind::MissingComma))
+        }
+        Some(c) => Err(iter.error(ErrorKind::UnexpectedChar(c))),
+        None => Err(iter.error(ErrorKind::UnexpectedEof)),
+    }
+}
+
+/// Parses an object key and the colon after it.
+fn parse_key<'s, I: Iterator<Item = char>>(
+    iter: &mut Reader<'_, 's, I>,
+) -> Result<(Cow<'s, str>, Position), ParseError> {
+    skip_whitespace(iter)?;
+    let key_at = iter.position();
+    let key = match iter.peek() {
+        Some(c) if iter.options.allow_unquoted_keys && is_identifier_start(c) => {
+            parse_identifier(iter, key_at)?
+        }
+        Some('\'') if iter.options.allow_single_quotes => parse_string(iter)?,
+        Some(c) if c != '"' => return Err(iter.error(ErrorKind::ExpectedStringKey)),
+        _ => parse_string(iter)?,
+    };
+    skip_whitespace(iter)?;
+    expect_char(iter, ':')?;
+
+    Ok((key, key_at))
+}
+
+fn is_identifier_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_' || c == '$'
+}
+
+/// Parses an unquoted key, with `allow_unquoted_keys`. The key is checked
+/// against `max_string_length` like a string would be.
+fn parse_identifier<'s, I: Iterator<Item = char>>(
+    iter: &mut Reader<'_, 's, I>,
+    key_at: Position,
+) -> Result<Cow<'s, str>, ParseError> {
+    let start = iter.begin_token();
+    while matches!(iter.peek(), Some(c) if is_identifier_start(c) || c.is_ascii_digit()) {
+        iter.next();
+    }
+    let key = iter.end_token(start);
+    if matches!(iter.options.max_string_length, Some(max) if key.len() > max) {
+        return Err(iter.error_at(ErrorKind::SizeLimitExceeded, key_at));
+    }
+
+    Ok(key)
+}
+
+fn insert_member<'s, I: Iterator<Item = char>, B: Build<'s>>(
+    iter: &Reader<'_, 's, I>,
+    build: &mut B,
+    object: &mut B::Object,
+    key: Cow<'s, str>,
+    value: B::Value,
+    key_at: Position,
+) -> Result<(), ParseError> {
+    match iter.options.duplicate_keys {
+        DuplicateKeys::Last => build.insert(object, key, value),
+        DuplicateKeys::First => {
+            if !build.contains_key(object, &key) {
+                build.insert(object, key, value);
+            }
+        }
+        DuplicateKeys::Error => {
+            if build.contains_key(object, &key) {
+                let kind = ErrorKind::DuplicateKey(key.into_owned());
+                return Err(iter.error_at(kind, key_at));
+            }
+            build.insert(object, key, value);
+        }
+    }
+
+    Ok(())
+}
+
+fn starts_value(c: char) -> bool {
+    matches!(c, '"' | '{' | '[' | 't' | 'f' | 'n' | '-') || c.is_ascii_digit()
+}
+
+fn parse_literal<I: Iterator<Item = char>>(
+    iter: &mut Reader<I>,
+    literal: &str,
+) -> Result<(), ParseError> {
+    for c in literal.chars() {
+        expect_char(iter, c)?;
+    }
+
+    Ok(())
+}
+
+/// Skips whitespace, and comments too when they are allowed.
+fn skip_whitespace<I: Iterator<Item = char>>(iter: &mut Reader<I>) -> Result<(), ParseError> {
+    while let Some(c) = iter.peek() {
+        if is_whitespace(c) {
+            iter.next();
+        } else if c == '/' && iter.options.allow_comments {
+            skip_comment(iter)?;
+        } else {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Only these four characters are whitespace in JSON, unlike the Unicode
+/// spaces that `char::is_whitespace` also accepts.
+fn is_whitespace(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\n' | '\r')
+}
+
+fn skip_comment<I: Iterator<Item = char>>(iter: &mut Reader<I>) -> Result<(), ParseError> {
+    let start = iter.position();
+    iter.next();
+
+    match iter.peek() {
+        Some('/') => {
+            while let Some(c) = iter.next() {
+                if c == '\n' {
+                    break;
+                }
+            }
+            Ok(())
+        }
+        Some('*') => {
+            iter.next();
+            let mut star = false;
+            while let Some(c) = iter.next() {
+                if star && c == '/' {
+                    return Ok(());
+                }
+                star = c == '*';
+            }
+            Err(iter.error_at(ErrorKind::UnterminatedComment, start))
+        }
+        Some(c) => Err(iter.error(ErrorKind::UnexpectedChar(c))),
+        None => Err(iter.error(ErrorKind::UnexpectedEof)),
+    }
+}
+
+/// Turns what the parser reads into values, so the same parsing code can build
+/// owned or borrowed trees.
+trait Build<'s> {
+    type Value;
+    type Object;
+
+    fn string(&mut self, s: Cow<'s, str>) -> Self::Value;
+    fn number(&mut self, n: f64) -> Self::Value;
+    fn integer(&mut self, n: i64) -> Self::Value;
+    /// The text of a number, with `raw_numbers`.
+    fn raw_number(&mut self, token: Cow<'s, str>) -> Self::Value;
+    fn bool(&mut self, b: bool) -> Self::Value;
+    fn null(&mut self) -> Self::Value;
+    fn array(&mut self, vec: Vec<Self::Value>) -> Self::Value;
+    fn new_object(&mut self) -> Self::Object;
+    fn contains_key(&self, object: &Self::Object, key: &str) -> bool;
+    /// Adds a member, replacing the value of an existing one in place.
+    fn insert(&mut self, object: &mut Self::Object, key: Cow<'s, str>, value: Self::Value);
+    fn object(&mut self, object: Self::Object) -> Self::Value;
+    /// Frees a value that won't be returned, like the partial tree left when
+    /// parsing fails.
+    fn discard(&mut self, value: Self::Value) {
+        drop(value);
+    }
+}
+
+/// Builds a `Value`, keeping the keys seen so far when they are interned.
+#[derive(Default)]
+struct OwnedTree {
+    keys: Option<LookupSet<Arc<str>>>,
+}
+
+impl OwnedTree {
+    fn new(options: &Options) -> Self {
+        OwnedTree {
+            keys: if options.intern_keys {
+                Some(LookupSet::new())
+            } else {
+                None
+            },
+        }
+    }
+}
+
+impl<'s> Build<'s> for OwnedTree {
+    type Value = Value;
+    type Object = Map;
+
+    fn string(&mut self, s: Cow<'s, str>) -> Value {
+        Value::String(s.into_owned())
+    }
+
+    fn number(&mut self, n: f64) -> Value {
+        Value::Number(n)
+    }
+
+    fn integer(&mut self, n: i64) -> Value {
+        Value::Integer(n)
+    }
+
+    fn raw_number(&mut self, token: Cow<'s, str>) -> Value {
+        Value::RawNumber(token.into_owned())
+    }
+
+    fn bool(&mut self, b: bool) -> Value {
+        if b {
+            Value::True
+        } else {
+            Value::False
+        }
+    }
+
+    fn null(&mut self) -> Value {
+        Value::Null
+    }
+
+    fn array(&mut self, vec: Vec<Value>) -> Value {
+        Value::Array(vec)
+    }
+
+    fn new_object(&mut self) -> Map {
+        Map::new()
+    }
+
+    fn contains_key(&self, map: &Map, key: &str) -> bool {
+        map.contains_key(key)
+    }
+
+    fn insert(&mut self, map: &mut Map, key: Cow<'s, str>, value: Value) {
+        let key: Arc<str> = match &mut self.keys {
+            Some(keys) => match keys.get(&*key) {
+                Some(interned) => interned.clone(),
+                None => {
+                    let key = Arc::from(&*key);
+                    keys.insert(Arc::clone(&key));
+                    key
+                }
+            },
+            None => Arc::from(&*key),
+        };
+        map.insert(key, value);
+    }
+
+    fn object(&mut self, map: Map) -> Value {
+        Value::Object(map)
+    }
+
+    fn discard(&mut self, value: Value) {
+        value.drop_deep();
+    }
+}
+
+/// Builds a `BorrowedValue`. Objects get a temporary index so duplicate keys
+/// are found without scanning the members.
+struct BorrowedTree;
+
+type BorrowedMembers<'s> = Vec<(Cow<'s, str>, BorrowedValue<'s>)>;
+
+impl<'s> Build<'s> for BorrowedTree {
+    type Value = BorrowedValue<'s>;
+    type Object = (BorrowedMembers<'s>, LookupMap<Cow<'s, str>, usize>);
+
+    fn string(&mut self, s: Cow<'s, str>) -> BorrowedValue<'s> {
+        BorrowedValue::String(s)
+    }
+
+    fn number(&mut self, n: f64) -> BorrowedValue<'s> {
+        BorrowedValue::Number(n)
+    }
+
+    fn integer(&mut self, n: i64) -> BorrowedValue<'s> {
+        BorrowedValue::Integer(n)
+    }
+
+    fn raw_number(&mut self, token: Cow<'s, str>) -> BorrowedValue<'s> {
+        BorrowedValue::RawNumber(token)
+    }
+
+    fn bool(&mut self, b: bool) -> BorrowedValue<'s> {
+        if b {
+            BorrowedValue::True
+        } else {
+            BorrowedValue::False
+        }
+    }
+
+    fn null(&mut self) -> BorrowedValue<'s> {
+        BorrowedValue::Null
+    }
+
+    fn array(&mut self, vec: Vec<BorrowedValue<'s>>) -> BorrowedValue<'s> {
+        BorrowedValue::Array(vec)
+    }
+
+    fn new_object(&mut self) -> Self::Object {
+        (Vec::new(), LookupMap::new())
+    }
+
+    fn contains_key(&self, (_, index): &Self::Object, key: &str) -> bool {
+        index.contains_key(key)
+    }
+
+    fn insert(
+        &mut self,
+        (members, index): &mut Self::Object,
+        key: Cow<'s, str>,
+        value: BorrowedValue<'s>,
+    ) {
+        match index.get(&key) {
+            Some(&i) => members[i].1 = value,
+            None => {
+                index.insert(key.clone(), members.len());
+                members.push((key, value));
+            }
+        }
+    }
+
+    fn object(&mut self, (members, _): Self::Object) -> BorrowedValue<'s> {
+        BorrowedValue::Object(members)
+    }
+}
+
+/// Builds nothing, for `validate`. Object keys are only collected when
+/// duplicate keys are an error.
+struct Validate {
+    track_keys: bool,
+}
+
+impl<'s> Build<'s> for Validate {
+    type Value = ();
+    type Object = Option<LookupSet<Cow<'s, str>>>;
+
+    fn string(&mut self, _: Cow<'s, str>) {}
+
+    fn number(&mut self, _: f64) {}
+
+    fn integer(&mut self, _: i64) {}
+
+    fn raw_number(&mut self, _: Cow<'s, str>) {}
+
+    fn bool(&mut self, _: bool) {}
+
+    fn null(&mut self) {}
+
+    // a `Vec<()>` never allocates
+    fn array(&mut self, _: Vec<()>) {}
+
+    fn new_object(&mut self) -> Self::Object {
+        if self.track_keys {
+            Some(LookupSet::new())
+        } else {
+            None
+        }
+    }
+
+    fn contains_key(&self, keys: &Self::Object, key: &str) -> bool {
+        keys.as_ref().is_some_and(|keys| keys.contains(key))
+    }
+
+    fn insert(&mut self, keys: &mut Self::Object, key: Cow<'s, str>, _: ()) {
+        if let Some(keys) = keys {
+            keys.insert(key);
+        }
+    }
+
+    fn object(&mut self, _: Self::Object) {}
+}
@@ -0,0 +1,189 @@
+use alloc::{string::String, vec::Vec};
+use core::str::Chars;
+
+use super::{
+    check_input_size, check_root, end_of_element, parse_key, parse_scalar, skip_bom,
+    skip_whitespace, OwnedTree, Parser, Reader, DEFAULT_OPTIONS,
+};
+use crate::{ErrorKind, ParseError, Value};
+
+/// A piece of a JSON document, as produced by `events`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    StartObject,
+    /// The key of the member whose value comes next.
+    Key(String),
+    EndObject,
+    StartArray,
+    EndArray,
+    Str(String),
+    Number(f64),
+    Integer(i64),
+    /// A number as written, with `Parser::raw_numbers`.
+    RawNumber(String),
+    Bool(bool),
+    Null,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Container {
+    Array,
+    Object,
+}
+
+#[derive(Clone, Copy)]
+enum State {
+    /// A value is expected next.
+    Value,
+    /// Right after `[` or `{`, where the container may be closed at once.
+    FirstElement,
+    FirstMember,
+    /// After a value, where a comma, a closing bracket or the end of the
+    /// document is expected.
+    AfterValue,
+}
+
+/// An iterator over the events of a single JSON document, see `events`.
+pub struct Events<'a> {
+    iter: Reader<'a, 'a, Chars<'a>>,
+    /// The kinds of the containers that are still open.
+    stack: Vec<Container>,
+    state: State,
+    /// An error found before reading anything, to be returned first.
+    pending: Option<ParseError>,
+    done: bool,
+}
+
+/// Reads a JSON document as a stream of events instead of building a tree.
+///
+/// Only the nesting of the open arrays and objects is kept in memory, so a
+/// huge document can be processed one piece at a time. The first error ends
+/// the iteration. Since members aren't collected, duplicate keys are passed on
+/// as they appear.
+pub fn events(input: &str) -> Events<'_> {
+    Events::new(Reader::from_str(input, &DEFAULT_OPTIONS))
+}
+
+impl Parser {
+    /// Reads a JSON document as a stream of events, with this parser's
+    /// options. See `json::events`.
+    pub fn events<'a>(&'a self, input: &'a str) -> Events<'a> {
+        let mut events = Events::new(Reader::from_str(input, &self.options));
+        events.pending = check_input_size(&self.options, input.len()).err();
+        events
+    }
+}
+
+impl<'a> Events<'a> {
+    fn new(mut iter: Reader<'a, 'a, Chars<'a>>) -> Self {
+        skip_bom(&mut iter);
+        Events {
+            iter,
+            stack: Vec::new(),
+            state: State::Value,
+            pending: None,
+            done: false,
+        }
+    }
+
+    /// Reads the next event, or `None` at the end of the document.
+    fn step(&mut self) -> Result<Option<Event>, ParseError> {
+        skip_whitespace(&mut self.iter)?;
+        match self.state {
+            State::Value => self.value(),
+            State::FirstElement if self.iter.peek() == Some(']') => self.close(),
+            State::FirstElement => self.value(),
+            State::FirstMember if self.iter.peek() == Some('}') => self.close(),
+            State::FirstMember => self.key(),
+            State::AfterValue => {
+                let close = match self.stack.last() {
+                    Some(Container::Array) => ']',
+                    Some(Container::Object) => '}',
+                    None if self.iter.peek().is_some() => {
+                        return Err(self.iter.error(ErrorKind::TrailingData))
+                    }
+                    None => return Ok(None),
+                };
+                if end_of_element(&mut self.iter, close)? {
+                    return self.closed();
+                }
+                match close {
+                    ']' => self.value(),
+                    _ => self.key(),
+                }
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Option<Event>, ParseError> {
+        if self.stack.is_empty() {
+            check_root(&mut self.iter)?;
+        }
+        let iter = &mut self.iter;
+        let (container, state, event) = match iter.peek() {
+            Some('[') => (Container::Array, State::FirstElement, Event::StartArray),
+            Some('{') => (Container::Object, State::FirstMember, Event::StartObject),
+            _ => {
+                let event = match parse_scalar(iter, &mut OwnedTree::default())? {
+                    Value::String(s) => Event::Str(s),
+                    Value::Number(n) => Event::Number(n),
+                    Value::Integer(n) => Event::Integer(n),
+                    Value::RawNumber(s) => Event::RawNumber(s),
+                    Value::True => Event::Bool(true),
+                    Value::False => Event::Bool(false),
+                    _ => Event::Null,
+                };
+                self.state = State::AfterValue;
+                return Ok(Some(event));
+            }
+        };
+
+        iter.enter()?;
+        iter.next();
+        self.stack.push(container);
+        self.state = state;
+        Ok(Some(event))
+    }
+
+    fn key(&mut self) -> Result<Option<Event>, ParseError> {
+        let (key, _) = parse_key(&mut self.iter)?;
+        self.state = State::Value;
+        Ok(Some(Event::Key(key.into_owned())))
+    }
+
+    /// Consumes the closing bracket of the innermost container.
+    fn close(&mut self) -> Result<Option<Event>, ParseError> {
+        self.iter.next();
+        self.closed()
+    }
+
+    /// Ends the innermost container once its bracket has been consumed.
+    fn closed(&mut self) -> Result<Option<Event>, ParseError> {
+        self.iter.leave();
+        self.state = State::AfterValue;
+        match self.stack.pop() {
+            Some(Container::Array) => Ok(Some(Event::EndArray)),
+            _ => Ok(Some(Event::EndObject)),
+        }
+    }
+}
+
+impl<'a> Iterator for Events<'a> {
+    type Item = Result<Event, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if let Some(err) = self.pending.take() {
+            self.done = true;
+            return Some(Err(err));
+        }
+
+        let result = self.step().transpose();
+        if !matches!(result, Some(Ok(_))) {
+            self.done = true;
+        }
+        result
+    }
+}
@@ -0,0 +1,224 @@
+use alloc::string::{String, ToString};
+use core::{fmt, mem};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::{
+    pointer::{parse_index, unescape},
+    Value,
+};
+
+/// Why an operation of a JSON Patch couldn't be applied.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchErrorKind {
+    /// The patch isn't an array of operations.
+    NotAnArray,
+    /// The operation lacks this member, or it has the wrong type, as with a
+    /// `path` that isn't a string.
+    InvalidMember(&'static str),
+    UnknownOp(String),
+    /// A pointer that is neither empty nor starts with `/`.
+    InvalidPointer(String),
+    /// Nothing is at this path, or for `add`, at the parent of this path.
+    PathNotFound(String),
+    /// A `move` from this path to a place inside of it.
+    MoveIntoChild(String),
+    /// A `test` found a different value at this path.
+    TestFailed(String),
+}
+
+/// A failed operation, by its index in the patch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatchError {
+    pub index: usize,
+    pub kind: PatchErrorKind,
+}
+
+impl fmt::Display for PatchErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PatchErrorKind::NotAnArray => f.write_str("patch is not an array"),
+            PatchErrorKind::InvalidMember(name) => write!(f, "missing or invalid \"{}\"", name),
+            PatchErrorKind::UnknownOp(op) => write!(f, "unknown operation {:?}", op),
+            PatchErrorKind::InvalidPointer(path) => write!(f, "invalid JSON Pointer {:?}", path),
+            PatchErrorKind::PathNotFound(path) => write!(f, "path {:?} not found", path),
+            PatchErrorKind::MoveIntoChild(path) => write!(f, "cannot move {:?} into itself", path),
+            PatchErrorKind::TestFailed(path) => write!(f, "test failed at {:?}", path),
+        }
+    }
+}
+
+/// Formats as e.g. `operation 2: path "/a/b" not found`.
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "operation {}: {}", self.index, self.kind)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for PatchError {}
+
+/// Applies a JSON Patch (RFC 6902) to `doc`.
+///
+/// The patch is an array of operations, each an object with an `op` and a
+/// `path`: `add`, `remove` and `replace` change the value at `path`, `move`
+/// and `copy` take it from the pointer `from`, and `test` checks that it
+/// equals `value`. Numbers are equal when they have the same value, so `1`
+/// passes a test against `1.0`.
+///
+/// Operations apply in order. The first one that fails, including a failed
+/// `test`, stops the patch and is returned, while the ones before it stay
+/// applied; to get all or nothing, patch a clone.
+///
+/// ```
+/// let mut doc = json::parse(r#"{"name": "web", "ports": [80]}"#).unwrap();
+/// let patch = json::parse(
+///     r#"[
+///         {"op": "test", "path": "/name", "value": "web"},
+///         {"op": "add", "path": "/ports/-", "value": 443},
+///         {"op": "move", "from": "/name", "path": "/service"}
+///     ]"#,
+/// )
+/// .unwrap();
+/// json::apply_patch(&mut doc, &patch).unwrap();
+/// assert_eq!(doc.to_string(), r#"{"ports":[80,443],"service":"web"}"#);
+///
+/// let patch = json::parse(r#"[{"op": "test", "path": "/service", "value": "db"}]"#).unwrap();
+/// let err = json::apply_patch(&mut doc, &patch).unwrap_err();
+/// assert_eq!(err.to_string(), r#"operation 0: test failed at "/service""#);
+/// ```
+pub fn apply_patch(doc: &mut Value, patch: &Value) -> Result<(), PatchError> {
+    let ops = patch.as_array().ok_or(PatchError {
+        index: 0,
+        kind: PatchErrorKind::NotAnArray,
+    })?;
+    for (index, op) in ops.iter().enumerate() {
+        apply_op(doc, op).map_err(|kind| PatchError { index, kind })?;
+    }
+
+    Ok(())
+}
+
+fn apply_op(doc: &mut Value, op: &Value) -> Result<(), PatchErrorKind> {
+    let member = |name| op.get(name).ok_or(PatchErrorKind::InvalidMember(name));
+    let pointer = |name| {
+        member(name)?
+            .as_str()
+            .ok_or(PatchErrorKind::InvalidMember(name))
+    };
+
+    let path = pointer("path")?;
+    match pointer("op")? {
+        "add" => add(doc, path, member("value")?.clone()).map_err(|(kind, _)| kind),
+        "remove" => remove(doc, path).map(drop),
+        "replace" => {
+            let value = member("value")?;
+            *find_mut(doc, path)? = value.clone();
+            Ok(())
+        }
+        "move" => {
+            let from = pointer("from")?;
+            if path == from {
+                return find(doc, from).map(drop);
+            }
+            if path.starts_with(from) && path[from.len()..].starts_with('/') {
+                return Err(PatchErrorKind::MoveIntoChild(from.to_string()));
+            }
+            let value = remove(doc, from)?;
+            // put the value back where it was rather than lose it
+            add(doc, path, value).map_err(|(kind, value)| {
+                let _ = add(doc, from, value);
+                kind
+            })
+        }
+        "copy" => {
+            let value = find(doc, pointer("from")?)?.clone();
+            add(doc, path, value).map_err(|(kind, _)| kind)
+        }
+        "test" => {
+            if find(doc, path)? == member("value")? {
+                Ok(())
+            } else {
+                Err(PatchErrorKind::TestFailed(path.to_string()))
+            }
+        }
+        op => Err(PatchErrorKind::UnknownOp(op.to_string())),
+    }
+}
+
+fn find<'v>(doc: &'v Value, path: &str) -> Result<&'v Value, PatchErrorKind> {
+    split(path)?;
+    doc.pointer(path)
+        .ok_or_else(|| PatchErrorKind::PathNotFound(path.to_string()))
+}
+
+fn find_mut<'v>(doc: &'v mut Value, path: &str) -> Result<&'v mut Value, PatchErrorKind> {
+    split(path)?;
+    doc.pointer_mut(path)
+        .ok_or_else(|| PatchErrorKind::PathNotFound(path.to_string()))
+}
+
+/// Splits a pointer into the pointer to its parent and its last token, or
+/// gives `None` for the whole document.
+fn split(path: &str) -> Result<Option<(&str, String)>, PatchErrorKind> {
+    if path.is_empty() {
+        return Ok(None);
+    }
+    match path.rfind('/') {
+        Some(i) if path.starts_with('/') => Ok(Some((&path[..i], unescape(&path[i + 1..])))),
+        _ => Err(PatchErrorKind::InvalidPointer(path.to_string())),
+    }
+}
+
+/// Adds `value` at `path`, handing it back if that fails.
+fn add(doc: &mut Value, path: &str, value: Value) -> Result<(), (PatchErrorKind, Value)> {
+    let not_found = || PatchErrorKind::PathNotFound(path.to_string());
+    let (parent, token) = match split(path) {
+        Ok(Some(split)) => split,
+        Ok(None) => {
+            *doc = value;
+            return Ok(());
+        }
+        Err(kind) => return Err((kind, value)),
+    };
+
+    match doc.pointer_mut(parent) {
+        Some(Value::Object(map)) => {
+            map.insert(token, value);
+            Ok(())
+        }
+        Some(Value::Array(vec)) => {
+            let index = match &*token {
+                "-" => Some(vec.len()),
+                token => parse_index(token).filter(|&i| i <= vec.len()),
+            };
+            match index {
+                Some(i) => {
+                    vec.insert(i, value);
+                    Ok(())
+                }
+                None => Err((not_found(), value)),
+            }
+        }
+        _ => Err((not_found(), value)),
+    }
+}
+
+/// Removes the value at `path` and returns it. Removing the whole document
+/// leaves `null`.
+fn remove(doc: &mut Value, path: &str) -> Result<Value, PatchErrorKind> {
+    let not_found = || PatchErrorKind::PathNotFound(path.to_string());
+    let (parent, token) = match split(path)? {
+        Some(split) => split,
+        None => return Ok(mem::take(doc)),
+    };
+
+    match doc.pointer_mut(parent) {
+        Some(Value::Object(map)) => map.remove(&token).ok_or_else(not_found),
+        Some(Value::Array(vec)) => match parse_index(&token) {
+            Some(i) if i < vec.len() => Ok(vec.remove(i)),
+            _ => Err(not_found()),
+        },
+        _ => Err(not_found()),
+    }
+}
@@ -0,0 +1,72 @@
+use alloc::string::String;
+
+use crate::Value;
+
+impl Value {
+    /// Looks up a value by JSON Pointer (RFC 6901), e.g. `/servers/0/host`.
+    ///
+    /// The empty pointer refers to the whole document. Returns `None` if the
+    /// pointer is malformed or any segment doesn't exist.
+    pub fn pointer(&self, ptr: &str) -> Option<&Value> {
+        if ptr.is_empty() {
+            return Some(self);
+        }
+        if !ptr.starts_with('/') {
+            return None;
+        }
+
+        ptr[1..]
+            .split('/')
+            .try_fold(self, |value, token| match value {
+                Value::Object(map) => map.get(&unescape(token)),
+                Value::Array(vec) => vec.get(parse_index(token)?),
+                _ => None,
+            })
+    }
+
+    /// Looks up a value by JSON Pointer like `pointer`, to change it in place.
+    /// Missing members and elements aren't created.
+    ///
+    /// ```
+    /// let mut value = json::parse(r#"{"a": {"b": [1, 2]}}"#).unwrap();
+    /// *value.pointer_mut("/a/b/0").unwrap() = json::Value::Integer(10);
+    /// assert_eq!(value.to_string(), r#"{"a":{"b":[10,2]}}"#);
+    /// assert!(value.pointer_mut("/a/c").is_none());
+    /// ```
+    pub fn pointer_mut(&mut self, ptr: &str) -> Option<&mut Value> {
+        if ptr.is_empty() {
+            return Some(self);
+        }
+        if !ptr.starts_with('/') {
+            return None;
+        }
+
+        ptr[1..]
+            .split('/')
+            .try_fold(self, |value, token| match value {
+                Value::Object(map) => map.get_mut(&unescape(token)),
+                Value::Array(vec) => vec.get_mut(parse_index(token)?),
+                _ => None,
+            })
+    }
+}
+
+pub(crate) fn unescape(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+pub(crate) fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Array indices are plain decimal numbers without leading zeros.
+pub(crate) fn parse_index(token: &str) -> Option<usize> {
+    if token.is_empty()
+        || (token.len() > 1 && token.starts_with('0'))
+        || !token.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+
+    token.parse().ok()
+}
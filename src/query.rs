@@ -0,0 +1,119 @@
+use alloc::{vec, vec::Vec};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::Value;
+
+/// A query that `Value::query` doesn't support, with the byte offset in the
+/// query where it went wrong.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryError {
+    pub offset: usize,
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unsupported query syntax at offset {}", self.offset)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for QueryError {}
+
+enum Segment<'a> {
+    Key(&'a str),
+    Index(usize),
+    Wildcard,
+}
+
+impl Value {
+    /// Finds every value matching a small subset of JSONPath, e.g.
+    /// `$.servers[*].host`.
+    ///
+    /// The query starts with `$`, the whole document, followed by any number
+    /// of these steps:
+    ///
+    /// - `.key` selects the member `key` of objects. The key runs up to the
+    ///   next `.` or `[` and can't be quoted.
+    /// - `[index]` selects an element of arrays, counting from 0.
+    /// - `.*` and `[*]` select every element of arrays and every member value
+    ///   of objects.
+    ///
+    /// Steps that don't apply to a value, like a key on an array, select
+    /// nothing. Any other syntax, including filters, slices, recursive descent
+    /// and negative indices, is rejected.
+    ///
+    /// ```
+    /// let config = json::parse(
+    ///     r#"{"servers": [{"host": "a", "port": 80}, {"host": "b"}]}"#,
+    /// ).unwrap();
+    ///
+    /// let hosts = config.query("$.servers[*].host").unwrap();
+    /// assert_eq!(hosts, [&json::Value::from("a"), &json::Value::from("b")]);
+    /// assert_eq!(config.query("$.servers[1].port").unwrap().len(), 0);
+    ///
+    /// // recursive descent isn't supported
+    /// assert_eq!(config.query("$..host").unwrap_err().offset, 2);
+    /// ```
+    pub fn query(&self, path: &str) -> Result<Vec<&Value>, QueryError> {
+        let mut found = vec![self];
+        for segment in parse_query(path)? {
+            found = found
+                .into_iter()
+                .flat_map(|value| select(value, &segment))
+                .collect();
+        }
+
+        Ok(found)
+    }
+}
+
+fn select<'v>(value: &'v Value, segment: &Segment) -> Vec<&'v Value> {
+    match (segment, value) {
+        (Segment::Key(key), Value::Object(map)) => map.get(key).into_iter().collect(),
+        (Segment::Index(i), Value::Array(vec)) => vec.get(*i).into_iter().collect(),
+        (Segment::Wildcard, Value::Object(map)) => map.values().collect(),
+        (Segment::Wildcard, Value::Array(vec)) => vec.iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn parse_query(path: &str) -> Result<Vec<Segment<'_>>, QueryError> {
+    if !path.starts_with('$') {
+        return Err(QueryError { offset: 0 });
+    }
+
+    let mut segments = Vec::new();
+    let mut offset = 1;
+    while offset < path.len() {
+        let rest = &path[offset..];
+        if let Some(rest) = rest.strip_prefix('.') {
+            let len = rest.find(['.', '[']).unwrap_or(rest.len());
+            segments.push(match &rest[..len] {
+                "" => return Err(QueryError { offset: offset + 1 }),
+                "*" => Segment::Wildcard,
+                key => Segment::Key(key),
+            });
+            offset += 1 + len;
+        } else if let Some(rest) = rest.strip_prefix('[') {
+            let len = rest.find(']').ok_or(QueryError { offset: path.len() })?;
+            let inner = &rest[..len];
+            segments.push(if inner == "*" {
+                Segment::Wildcard
+            } else if !inner.is_empty() && inner.bytes().all(|b| b.is_ascii_digit()) {
+                let index = inner
+                    .parse()
+                    .map_err(|_| QueryError { offset: offset + 1 })?;
+                Segment::Index(index)
+            } else {
+                return Err(QueryError { offset: offset + 1 });
+            });
+            offset += len + 2;
+        } else {
+            return Err(QueryError { offset });
+        }
+    }
+
+    Ok(segments)
+}
@@ -0,0 +1,92 @@
+use std::io::{BufReader, Bytes, Read};
+
+use crate::ErrorKind;
+
+/// Decodes UTF-8 from a byte stream one character at a time.
+///
+/// Iteration stops at the first read error, invalid sequence or byte past
+/// `limit`, which is then left in `error`.
+pub(crate) struct IoChars<R: Read> {
+    bytes: Bytes<BufReader<R>>,
+    limit: Option<usize>,
+    read: usize,
+    pub(crate) error: Option<ErrorKind>,
+}
+
+impl<R: Read> IoChars<R> {
+    pub(crate) fn new(reader: R, limit: Option<usize>) -> Self {
+        IoChars {
+            bytes: BufReader::new(reader).bytes(),
+            limit,
+            read: 0,
+            error: None,
+        }
+    }
+
+    fn next_byte(&mut self) -> Result<Option<u8>, ErrorKind> {
+        let byte = self.bytes.next().transpose().map_err(ErrorKind::Io)?;
+        if byte.is_some() {
+            self.read += 1;
+            if matches!(self.limit, Some(limit) if self.read > limit) {
+                return Err(ErrorKind::SizeLimitExceeded);
+            }
+        }
+
+        Ok(byte)
+    }
+
+    fn decode(&mut self) -> Result<Option<char>, ErrorKind> {
+        let first = match self.next_byte()? {
+            Some(b) => b,
+            None => return Ok(None),
+        };
+        let width = match first {
+            0x00..=0x7F => return Ok(Some(first as char)),
+            0xC2..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF4 => 4,
+            _ => return Err(ErrorKind::InvalidUtf8),
+        };
+
+        // a sequence may be split across reads, BufReader takes care of that
+        let mut buf = [first, 0, 0, 0];
+        for len in 1..width {
+            match self.next_byte()? {
+                Some(b) => buf[len] = b,
+                None => return Err(truncated(&buf[..len])),
+            }
+        }
+
+        match std::str::from_utf8(&buf[..width]) {
+            Ok(s) => Ok(s.chars().next()),
+            Err(_) => Err(ErrorKind::InvalidUtf8),
+        }
+    }
+}
+
+/// The error for a stream that ended after the first bytes of a sequence:
+/// the end of input if they could still have made up a character.
+fn truncated(bytes: &[u8]) -> ErrorKind {
+    match std::str::from_utf8(bytes) {
+        Err(err) if err.error_len().is_none() => ErrorKind::UnexpectedEof,
+        _ => ErrorKind::InvalidUtf8,
+    }
+}
+
+impl<R: Read> Iterator for IoChars<R> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.error.is_some() {
+            return None;
+        }
+
+        match self.decode() {
+            Ok(c) => c,
+            Err(kind) => {
+                self.error = Some(kind);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,362 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Write};
+#[cfg(feature = "std")]
+use std::io;
+
+use crate::{to_canonical_string, ParseError, Value};
+
+/// Serializes the value as compact JSON, the same as `Format::Compact`.
+///
+/// Parsing the output gives back a value equal to the original, with one
+/// exception: JSON has no way to represent NaN or the infinities, so
+/// non-finite numbers are written as `null`, the same as JavaScript's
+/// `JSON.stringify`.
+///
+/// Floats are written with the fewest digits that still parse back to the
+/// same number, in exponent notation when they are very large or very small.
+/// Whole floats lose their fraction, so `3.0` is written as `3` and comes back
+/// as an integer, which compares equal.
+///
+/// ```
+/// use json::Value;
+///
+/// assert_eq!(Value::Number(0.1).to_string(), "0.1");
+/// assert_eq!(Value::Number(3.0).to_string(), "3");
+/// assert_eq!(Value::Number(-0.0).to_string(), "-0");
+/// assert_eq!(Value::Number(1e21).to_string(), "1e21");
+/// assert_eq!(Value::Number(1.5e-7).to_string(), "1.5e-7");
+/// // the nearest float to 2^60 + 1 is 2^60
+/// let big = Value::Number(1152921504606846977.0);
+/// assert_eq!(big.to_string(), "1.152921504606847e18");
+/// assert_eq!(big.to_string().parse::<Value>().unwrap(), big);
+/// ```
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Serializer::new().write_value(f, self, None, 0)
+    }
+}
+
+/// A JSON serializer with configurable output. `Serializer::new()` writes the
+/// same as `Display` and the free `to_string_pretty` and `to_writer`
+/// functions.
+#[derive(Debug, Clone, Default)]
+pub struct Serializer {
+    escape_js_line_terminators: bool,
+    ensure_ascii: bool,
+    sort_keys: bool,
+}
+
+impl Serializer {
+    pub fn new() -> Self {
+        Serializer::default()
+    }
+
+    /// Escapes U+2028 LINE SEPARATOR and U+2029 PARAGRAPH SEPARATOR as
+    /// `\u2028` and `\u2029`. JSON allows them in strings but JavaScript
+    /// before ES2019 doesn't, so output embedded in a `<script>` tag or
+    /// evaluated as code could break. Off by default.
+    pub fn escape_js_line_terminators(mut self, escape: bool) -> Self {
+        self.escape_js_line_terminators = escape;
+        self
+    }
+
+    /// Escapes every character outside ASCII as `\uXXXX`, with a surrogate
+    /// pair for those outside the Basic Multilingual Plane, so the output is
+    /// pure ASCII. Off by default.
+    ///
+    /// ```
+    /// let value = json::Value::from("café 😀");
+    /// let ascii = json::Serializer::new().ensure_ascii(true);
+    /// assert_eq!(ascii.to_string(&value), r#""caf\u00e9 \ud83d\ude00""#);
+    /// ```
+    pub fn ensure_ascii(mut self, ensure: bool) -> Self {
+        self.ensure_ascii = ensure;
+        self
+    }
+
+    /// Writes object members sorted by key, comparing them as strings,
+    /// instead of in their stored order. Off by default.
+    ///
+    /// Unlike `to_canonical_string`, numbers and strings are written as
+    /// usual.
+    ///
+    /// ```
+    /// let value = json::parse(r#"{"b":1,"a":2}"#).unwrap();
+    /// let sorted = json::Serializer::new().sort_keys(true);
+    /// assert_eq!(sorted.to_string(&value), r#"{"a":2,"b":1}"#);
+    /// assert_eq!(sorted.to_string_pretty(&value, 2), "{\n  \"a\": 2,\n  \"b\": 1\n}");
+    /// ```
+    pub fn sort_keys(mut self, sort: bool) -> Self {
+        self.sort_keys = sort;
+        self
+    }
+
+    /// Serializes the value as compact JSON.
+    pub fn to_string(&self, value: &Value) -> String {
+        let mut out = String::new();
+        // writing into a String cannot fail
+        self.write_value(&mut out, value, None, 0).unwrap();
+        out
+    }
+
+    /// Serializes the value as JSON with every member and element on its own
+    /// line, indented by `indent` spaces per nesting level.
+    pub fn to_string_pretty(&self, value: &Value, indent: usize) -> String {
+        let mut out = String::new();
+        self.write_value(&mut out, value, Some(indent), 0).unwrap();
+        out
+    }
+
+    /// Serializes the value as compact JSON straight into `writer`. See
+    /// `json::to_writer`.
+    #[cfg(feature = "std")]
+    pub fn to_writer<W: io::Write>(&self, writer: &mut W, value: &Value) -> io::Result<()> {
+        let mut w = IoWriter::new(writer);
+        let result = self.write_value(&mut w, value, None, 0);
+        w.finish(result)
+    }
+
+    /// Serializes the value like `to_string_pretty`, straight into `writer`.
+    #[cfg(feature = "std")]
+    pub fn to_writer_pretty<W: io::Write>(
+        &self,
+        writer: &mut W,
+        value: &Value,
+        indent: usize,
+    ) -> io::Result<()> {
+        let mut w = IoWriter::new(writer);
+        let result = self.write_value(&mut w, value, Some(indent), 0);
+        w.finish(result)
+    }
+
+    /// Writes a value at nesting depth `level`, compact without an `indent`
+    /// and pretty with one.
+    fn write_value<W: Write>(
+        &self,
+        w: &mut W,
+        value: &Value,
+        indent: Option<usize>,
+        level: usize,
+    ) -> fmt::Result {
+        match value {
+            Value::Object(map) if !map.is_empty() => {
+                w.write_char('{')?;
+                if self.sort_keys {
+                    let mut members: Vec<_> = map.iter().collect();
+                    members.sort_unstable_by_key(|&(key, _)| key);
+                    self.write_members(w, members.into_iter(), indent, level)?;
+                } else {
+                    self.write_members(w, map.iter(), indent, level)?;
+                }
+                write_newline(w, indent, level)?;
+                w.write_char('}')
+            }
+            Value::Array(vec) if !vec.is_empty() => {
+                w.write_char('[')?;
+                for (i, value) in vec.iter().enumerate() {
+                    if i > 0 {
+                        w.write_char(',')?;
+                    }
+                    write_newline(w, indent, level + 1)?;
+                    self.write_value(w, value, indent, level + 1)?;
+                }
+                write_newline(w, indent, level)?;
+                w.write_char(']')
+            }
+            Value::Object(_) => w.write_str("{}"),
+            Value::Array(_) => w.write_str("[]"),
+            Value::String(s) => write_escaped(w, s, self),
+            Value::Number(n) => write_number(w, *n),
+            Value::Integer(n) => write!(w, "{}", n),
+            Value::RawNumber(s) => w.write_str(s),
+            Value::True => w.write_str("true"),
+            Value::False => w.write_str("false"),
+            Value::Null => w.write_str("null"),
+        }
+    }
+
+    fn write_members<'v, W: Write>(
+        &self,
+        w: &mut W,
+        members: impl Iterator<Item = (&'v str, &'v Value)>,
+        indent: Option<usize>,
+        level: usize,
+    ) -> fmt::Result {
+        for (i, (key, value)) in members.enumerate() {
+            if i > 0 {
+                w.write_char(',')?;
+            }
+            write_newline(w, indent, level + 1)?;
+            write_escaped(w, key, self)?;
+            w.write_str(if indent.is_some() { ": " } else { ":" })?;
+            self.write_value(w, value, indent, level + 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A layout for `serialize` to write JSON in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// No whitespace at all, as written by `Display`.
+    Compact,
+    /// Every member and element on a line of its own, indented by `indent`
+    /// spaces per nesting level, with a space after each colon. Empty arrays
+    /// and objects stay on one line as `[]` and `{}`. There is no newline
+    /// after the last bracket. As written by `to_string_pretty`.
+    Pretty { indent: usize },
+    /// The JSON Canonicalization Scheme (RFC 8785), as written by
+    /// `to_canonical_string`: compact, members sorted, numbers as ECMAScript
+    /// prints them.
+    Canonical,
+}
+
+/// Serializes the value in the given format.
+///
+/// ```
+/// use json::Format;
+///
+/// let value = json::parse(r#"{"a": [1, {}], "b": 1e21}"#).unwrap();
+/// assert_eq!(json::serialize(&value, Format::Compact), r#"{"a":[1,{}],"b":1e21}"#);
+/// assert_eq!(
+///     json::serialize(&value, Format::Pretty { indent: 2 }),
+///     "{\n  \"a\": [\n    1,\n    {}\n  ],\n  \"b\": 1e21\n}",
+/// );
+/// assert_eq!(json::serialize(&value, Format::Canonical), r#"{"a":[1,{}],"b":1e+21}"#);
+/// ```
+pub fn serialize(value: &Value, format: Format) -> String {
+    match format {
+        Format::Compact => value.to_string(),
+        Format::Pretty { indent } => to_string_pretty(value, indent),
+        Format::Canonical => to_canonical_string(value),
+    }
+}
+
+/// Serializes the value as JSON with every member and element on its own line,
+/// indented by `indent` spaces per nesting level.
+pub fn to_string_pretty(value: &Value, indent: usize) -> String {
+    Serializer::new().to_string_pretty(value, indent)
+}
+
+/// Serializes the value as compact JSON straight into `writer`, without
+/// building the whole string first.
+///
+/// The output is written in many small pieces, so an unbuffered writer such as
+/// a `File` should be wrapped in a `BufWriter`.
+#[cfg(feature = "std")]
+pub fn to_writer<W: io::Write>(writer: &mut W, value: &Value) -> io::Result<()> {
+    Serializer::new().to_writer(writer, value)
+}
+
+/// Serializes the value like `to_string_pretty`, straight into `writer`.
+#[cfg(feature = "std")]
+pub fn to_writer_pretty<W: io::Write>(
+    writer: &mut W,
+    value: &Value,
+    indent: usize,
+) -> io::Result<()> {
+    Serializer::new().to_writer_pretty(writer, value, indent)
+}
+
+/// Lets the `fmt::Write` based serializer write to an `io::Write`, keeping the
+/// I/O error that `fmt::Error` can't carry.
+#[cfg(feature = "std")]
+struct IoWriter<'a, W: io::Write> {
+    inner: &'a mut W,
+    error: Option<io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<'a, W: io::Write> IoWriter<'a, W> {
+    fn new(inner: &'a mut W) -> Self {
+        IoWriter { inner, error: None }
+    }
+
+    fn finish(self, result: fmt::Result) -> io::Result<()> {
+        match (result, self.error) {
+            (Ok(()), _) => Ok(()),
+            (Err(_), Some(err)) => Err(err),
+            (Err(_), None) => Err(io::Error::other("formatting failed")),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, W: io::Write> Write for IoWriter<'a, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
+
+/// Parses `input` and writes it back without any insignificant whitespace.
+pub fn minify(input: &str) -> Result<String, ParseError> {
+    Ok(crate::parse(input)?.to_string())
+}
+
+/// Starts a new line indented for `level`, when writing pretty JSON.
+fn write_newline<W: Write>(w: &mut W, indent: Option<usize>, level: usize) -> fmt::Result {
+    if let Some(indent) = indent {
+        w.write_char('\n')?;
+        for _ in 0..indent * level {
+            w.write_char(' ')?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_number<W: Write>(w: &mut W, n: f64) -> fmt::Result {
+    if !n.is_finite() {
+        // JSON has no representation for NaN and the infinities
+        w.write_str("null")
+    } else if !(-9007199254740992.0 < n && n < 9007199254740992.0)
+        || (n != 0.0 && -1e-6 < n && n < 1e-6)
+    {
+        // whole floats past 2^53 would be printed with made-up trailing
+        // zeros, which parse back as a different integer, and tiny ones with
+        // a long run of leading zeros
+        write!(w, "{:e}", n)
+    } else {
+        write!(w, "{}", n)
+    }
+}
+
+/// Writes `s` as a quoted JSON string. This is the inverse of the parser's
+/// string handling, using the short escapes where JSON has one.
+pub(crate) fn write_string<W: Write>(w: &mut W, s: &str) -> fmt::Result {
+    write_escaped(w, s, &Serializer::new())
+}
+
+/// Writes a quoted string with the extra escapes `ser` asks for.
+fn write_escaped<W: Write>(w: &mut W, s: &str, ser: &Serializer) -> fmt::Result {
+    w.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => w.write_str("\\\"")?,
+            '\\' => w.write_str("\\\\")?,
+            '\n' => w.write_str("\\n")?,
+            '\t' => w.write_str("\\t")?,
+            '\r' => w.write_str("\\r")?,
+            '\x08' => w.write_str("\\b")?,
+            '\x0C' => w.write_str("\\f")?,
+            c if c < '\u{20}' => write!(w, "\\u{:04x}", c as u32)?,
+            '\u{2028}' | '\u{2029}' if ser.escape_js_line_terminators => {
+                write!(w, "\\u{:04x}", c as u32)?
+            }
+            c if !c.is_ascii() && ser.ensure_ascii => {
+                for unit in c.encode_utf16(&mut [0; 2]) {
+                    write!(w, "\\u{:04x}", unit)?;
+                }
+            }
+            c => w.write_char(c)?,
+        }
+    }
+    w.write_char('"')
+}
@@ -0,0 +1,164 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{convert::TryFrom, fmt};
+
+use serde::{
+    de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor},
+    ser::{Serialize, SerializeMap, SerializeSeq, Serializer},
+};
+
+use crate::{value::raw_number_value, Map, Value};
+
+/// Serializes the value as the JSON it stands for, so any serde format can
+/// write it.
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Object(map) => {
+                let mut members = serializer.serialize_map(Some(map.len()))?;
+                for (key, value) in map.iter() {
+                    members.serialize_entry(key, value)?;
+                }
+                members.end()
+            }
+            Value::Array(vec) => {
+                let mut elements = serializer.serialize_seq(Some(vec.len()))?;
+                for value in vec {
+                    elements.serialize_element(value)?;
+                }
+                elements.end()
+            }
+            Value::String(s) => serializer.serialize_str(s),
+            Value::Number(n) => serializer.serialize_f64(*n),
+            Value::Integer(n) => serializer.serialize_i64(*n),
+            Value::RawNumber(s) => raw_number_value(s).serialize(serializer),
+            Value::True => serializer.serialize_bool(true),
+            Value::False => serializer.serialize_bool(false),
+            Value::Null => serializer.serialize_unit(),
+        }
+    }
+}
+
+/// Deserializes any self-describing serde data into a value. Unsigned
+/// integers past `i64::MAX` become floats, as they do when parsing.
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Value, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any JSON value")
+    }
+
+    fn visit_bool<E>(self, b: bool) -> Result<Value, E> {
+        Ok(if b { Value::True } else { Value::False })
+    }
+
+    fn visit_i64<E>(self, n: i64) -> Result<Value, E> {
+        Ok(Value::Integer(n))
+    }
+
+    fn visit_u64<E>(self, n: u64) -> Result<Value, E> {
+        match i64::try_from(n) {
+            Ok(n) => Ok(Value::Integer(n)),
+            Err(_) => Ok(Value::Number(n as f64)),
+        }
+    }
+
+    fn visit_f64<E>(self, n: f64) -> Result<Value, E> {
+        Ok(Value::Number(n))
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Value, E> {
+        Ok(Value::String(s.to_string()))
+    }
+
+    fn visit_string<E>(self, s: String) -> Result<Value, E> {
+        Ok(Value::String(s))
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        Deserialize::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut vec = Vec::new();
+        while let Some(value) = seq.next_element()? {
+            vec.push(value);
+        }
+        Ok(Value::Array(vec))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Value, A::Error> {
+        let mut map = Map::new();
+        while let Some((key, value)) = access.next_entry::<String, Value>()? {
+            map.insert(key, value);
+        }
+        Ok(Value::Object(map))
+    }
+}
+
+impl From<serde_json::Value> for Value {
+    fn from(value: serde_json::Value) -> Value {
+        match value {
+            serde_json::Value::Object(members) => {
+                let mut map = Map::new();
+                for (key, value) in members {
+                    map.insert(key, value.into());
+                }
+                Value::Object(map)
+            }
+            serde_json::Value::Array(vec) => {
+                Value::Array(vec.into_iter().map(Value::from).collect())
+            }
+            serde_json::Value::String(s) => Value::String(s),
+            serde_json::Value::Number(n) => match n.as_i64() {
+                Some(n) => Value::Integer(n),
+                None => n.as_f64().map_or(Value::Null, Value::Number),
+            },
+            serde_json::Value::Bool(true) => Value::True,
+            serde_json::Value::Bool(false) => Value::False,
+            serde_json::Value::Null => Value::Null,
+        }
+    }
+}
+
+/// Non-finite numbers become `null`, as they do when serializing.
+impl From<Value> for serde_json::Value {
+    fn from(value: Value) -> serde_json::Value {
+        match value {
+            Value::Object(map) => serde_json::Value::Object(
+                map.into_iter()
+                    .map(|(key, value)| (key.to_string(), value.into()))
+                    .collect(),
+            ),
+            Value::Array(vec) => {
+                serde_json::Value::Array(vec.into_iter().map(Into::into).collect())
+            }
+            Value::String(s) => serde_json::Value::String(s),
+            Value::Number(n) => serde_json::Number::from_f64(n)
+                .map_or(serde_json::Value::Null, serde_json::Value::Number),
+            Value::Integer(n) => serde_json::Value::from(n),
+            Value::RawNumber(s) => raw_number_value(&s).into(),
+            Value::True => serde_json::Value::Bool(true),
+            Value::False => serde_json::Value::Bool(false),
+            Value::Null => serde_json::Value::Null,
+        }
+    }
+}
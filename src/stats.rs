@@ -0,0 +1,74 @@
+use crate::Value;
+
+/// Counts of what a `Value` is made of, see `Value::stats`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Stats {
+    pub objects: usize,
+    pub arrays: usize,
+    pub strings: usize,
+    /// Integers and floats alike.
+    pub numbers: usize,
+    pub booleans: usize,
+    pub nulls: usize,
+    /// The most arrays and objects nested inside each other, counted the same
+    /// way as `Parser::with_max_depth`: 0 for a scalar, 1 for `[]`.
+    pub max_depth: usize,
+    /// The members of all the objects together.
+    pub members: usize,
+    /// The elements of all the arrays together.
+    pub elements: usize,
+}
+
+impl Value {
+    /// Walks the whole tree and counts its values by type, e.g. to pick sane
+    /// limits for untrusted input.
+    ///
+    /// ```
+    /// let value = json::parse(
+    ///     r#"{"a": [1, 2.5, "x", null], "b": {"c": true, "d": false}}"#,
+    /// ).unwrap();
+    /// let stats = value.stats();
+    /// assert_eq!(stats.objects, 2);
+    /// assert_eq!(stats.arrays, 1);
+    /// assert_eq!(stats.strings, 1);
+    /// assert_eq!(stats.numbers, 2);
+    /// assert_eq!(stats.booleans, 2);
+    /// assert_eq!(stats.nulls, 1);
+    /// assert_eq!(stats.max_depth, 2);
+    /// assert_eq!(stats.members, 4);
+    /// assert_eq!(stats.elements, 4);
+    ///
+    /// assert_eq!(json::Value::Null.stats().max_depth, 0);
+    /// ```
+    pub fn stats(&self) -> Stats {
+        let mut stats = Stats::default();
+        count(self, 0, &mut stats);
+        stats
+    }
+}
+
+/// Adds `value`, found inside `depth` arrays and objects, to `stats`.
+fn count(value: &Value, depth: usize, stats: &mut Stats) {
+    match value {
+        Value::Object(map) => {
+            stats.objects += 1;
+            stats.members += map.len();
+            stats.max_depth = stats.max_depth.max(depth + 1);
+            for value in map.values() {
+                count(value, depth + 1, stats);
+            }
+        }
+        Value::Array(vec) => {
+            stats.arrays += 1;
+            stats.elements += vec.len();
+            stats.max_depth = stats.max_depth.max(depth + 1);
+            for value in vec {
+                count(value, depth + 1, stats);
+            }
+        }
+        Value::String(_) => stats.strings += 1,
+        Value::Number(_) | Value::Integer(_) | Value::RawNumber(_) => stats.numbers += 1,
+        Value::True | Value::False => stats.booleans += 1,
+        Value::Null => stats.nulls += 1,
+    }
+}
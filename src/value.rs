@@ -0,0 +1,598 @@
+use alloc::{
+    string::{String, ToString},
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
+use core::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    ops::Index,
+    str::FromStr,
+};
+
+use crate::{Map, ParseError};
+
+/// A parsed JSON value.
+///
+/// JSON has a single number type. The parser keeps numbers written without a
+/// fraction or exponent as `Integer` when they fit in an `i64`, and everything
+/// else becomes a `Number`. Both are written without a decimal point when
+/// they are whole, so serializing a `Number(3.0)` gives `3`, which parses
+/// back as `Integer(3)`. An integer and a float holding the same number are
+/// equal, so this round trip gives back an equal value, and serializing it
+/// again gives the same text.
+///
+/// ```
+/// use json::Value;
+///
+/// let value = json::parse("[42, 3.0, 2.5]").unwrap();
+/// assert_eq!(value[0], Value::Integer(42));
+/// assert_eq!(value.to_string(), "[42,3,2.5]");
+///
+/// let again = json::parse(&value.to_string()).unwrap();
+/// assert_eq!(again[1], Value::Integer(3));
+/// assert_eq!(again, value);
+/// assert_eq!(again.to_string(), value.to_string());
+/// ```
+#[derive(Debug, Clone)]
+pub enum Value {
+    Object(Map),
+    Array(Vec<Value>),
+    String(String),
+    /// Any number that isn't an `Integer`, rounded to the nearest `f64`.
+    /// Numbers past its range become infinite, and ones too close to zero
+    /// become zero, however long their exponent.
+    ///
+    /// ```
+    /// let value = json::parse("[1e308, 1e400, -1e400, 1e-400, 5e-324, 1e99999999999999999999]");
+    /// let value = value.unwrap();
+    /// assert_eq!(value[0].as_f64(), Some(1e308));
+    /// assert_eq!(value[1].as_f64(), Some(f64::INFINITY));
+    /// assert_eq!(value[2].as_f64(), Some(f64::NEG_INFINITY));
+    /// assert_eq!(value[3].as_f64(), Some(0.0));
+    /// assert_eq!(value[4].as_f64(), Some(f64::from_bits(1)));
+    /// assert_eq!(value[5].as_f64(), Some(f64::INFINITY));
+    /// ```
+    Number(f64),
+    /// A number written without a fraction or exponent that fits in an
+    /// `i64`, kept exact.
+    Integer(i64),
+    /// A number exactly as written in the input, kept by
+    /// `Parser::raw_numbers` so it can be written back unchanged. It compares
+    /// equal to the `Integer` or `Number` the parser would have read. It is
+    /// serialized as is, so when building one it has to be valid JSON.
+    RawNumber(String),
+    True,
+    False,
+    Null,
+}
+
+impl Value {
+    /// An empty object. To build one with members, fill a `Map` and convert it
+    /// with `Value::from`.
+    pub fn object() -> Value {
+        Value::Object(Map::new())
+    }
+
+    /// An empty array.
+    pub fn array() -> Value {
+        Value::Array(Vec::new())
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the number as a float, converting integers.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            Value::Integer(n) => Some(*n as f64),
+            Value::RawNumber(s) => raw_number_value(s).as_f64(),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::True => Some(true),
+            Value::False => Some(false),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<Value>> {
+        match self {
+            Value::Array(vec) => Some(vec),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&Map> {
+        match self {
+            Value::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    pub fn as_array_mut(&mut self) -> Option<&mut Vec<Value>> {
+        match self {
+            Value::Array(vec) => Some(vec),
+            _ => None,
+        }
+    }
+
+    pub fn as_object_mut(&mut self) -> Option<&mut Map> {
+        match self {
+            Value::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    pub fn is_object(&self) -> bool {
+        matches!(self, Value::Object(_))
+    }
+
+    pub fn is_array(&self) -> bool {
+        matches!(self, Value::Array(_))
+    }
+
+    pub fn is_string(&self) -> bool {
+        matches!(self, Value::String(_))
+    }
+
+    /// True for both floats and integers.
+    pub fn is_number(&self) -> bool {
+        matches!(
+            self,
+            Value::Number(_) | Value::Integer(_) | Value::RawNumber(_)
+        )
+    }
+
+    pub fn is_boolean(&self) -> bool {
+        matches!(self, Value::True | Value::False)
+    }
+
+    /// The JSON type of the value, for messages: `"object"`, `"array"`,
+    /// `"string"`, `"number"`, `"boolean"` or `"null"`.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Object(_) => "object",
+            Value::Array(_) => "array",
+            Value::String(_) => "string",
+            Value::Number(_) | Value::Integer(_) | Value::RawNumber(_) => "number",
+            Value::True | Value::False => "boolean",
+            Value::Null => "null",
+        }
+    }
+
+    /// Looks up an object member, giving `None` when the member is missing or
+    /// the value isn't an object.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.as_object()?.get(key)
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value> {
+        match self {
+            Value::Object(map) => map.get_mut(key),
+            _ => None,
+        }
+    }
+
+    /// Looks up an array element, giving `None` when the index is out of bounds
+    /// or the value isn't an array.
+    pub fn get_index(&self, index: usize) -> Option<&Value> {
+        self.as_array()?.get(index)
+    }
+
+    pub fn get_index_mut(&mut self, index: usize) -> Option<&mut Value> {
+        match self {
+            Value::Array(vec) => vec.get_mut(index),
+            _ => None,
+        }
+    }
+
+    pub fn array_iter(&self) -> Option<impl Iterator<Item = &Value>> {
+        self.as_array().map(|vec| vec.iter())
+    }
+
+    pub fn object_iter(&self) -> Option<impl Iterator<Item = (&str, &Value)>> {
+        self.as_object().map(|map| map.iter())
+    }
+
+    /// Inserts a member into an object and returns the value it replaced,
+    /// like `Map::insert`. A `Null` value becomes an empty object first, so a
+    /// document can be built up from `Value::default()`.
+    ///
+    /// # Panics
+    ///
+    /// If the value is neither an object nor `Null`.
+    ///
+    /// ```
+    /// use json::Value;
+    ///
+    /// let mut value = Value::Null;
+    /// assert_eq!(value.insert("name", Value::from("x")), None);
+    /// assert_eq!(value.insert("name", Value::from("y")), Some(Value::from("x")));
+    /// assert_eq!(value.to_string(), r#"{"name":"y"}"#);
+    /// ```
+    pub fn insert(&mut self, key: impl Into<Arc<str>>, value: Value) -> Option<Value> {
+        if self.is_null() {
+            *self = Value::object();
+        }
+        match self {
+            Value::Object(map) => map.insert(key, value),
+            _ => panic!("can't insert a member into {}", self.type_name()),
+        }
+    }
+
+    /// Moves the value out, leaving `Null` in its place. Together with
+    /// `get_mut` this moves parts of a document around without cloning them.
+    ///
+    /// ```
+    /// use json::Value;
+    ///
+    /// let mut value = json::parse(r#"{"data": {"items": [1, 2]}}"#).unwrap();
+    /// let items = value.get_mut("data").unwrap().get_mut("items").unwrap().take();
+    /// assert_eq!(items.to_string(), "[1,2]");
+    /// assert_eq!(value.to_string(), r#"{"data":{"items":null}}"#);
+    /// ```
+    pub fn take(&mut self) -> Value {
+        core::mem::take(self)
+    }
+
+    /// Puts `value` in place of this one and returns the old value.
+    pub fn replace(&mut self, value: Value) -> Value {
+        core::mem::replace(self, value)
+    }
+
+    /// Drops the value one array or object at a time. Plain dropping recurses
+    /// once per level of nesting, which can overflow the stack on documents
+    /// parsed with a depth limit far above the default.
+    ///
+    /// ```
+    /// let depth = 100_000;
+    /// let deep = json::Parser::new()
+    ///     .with_max_depth(usize::MAX)
+    ///     .parse(&("[".repeat(depth) + &"]".repeat(depth)))
+    ///     .unwrap();
+    /// deep.drop_deep();
+    /// ```
+    pub fn drop_deep(self) {
+        let mut stack = vec![self];
+        while let Some(mut value) = stack.pop() {
+            // the children are moved out first, so dropping the value
+            // doesn't go any deeper
+            match &mut value {
+                Value::Array(vec) => stack.append(vec),
+                Value::Object(map) => {
+                    stack.extend(core::mem::take(map).into_iter().map(|(_, v)| v))
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Iterates over the elements of an array. Any other value yields nothing.
+impl<'a> IntoIterator for &'a Value {
+    type Item = &'a Value;
+    type IntoIter = core::slice::Iter<'a, Value>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match self {
+            Value::Array(vec) => vec.iter(),
+            _ => [].iter(),
+        }
+    }
+}
+
+/// The default value is `Null`.
+impl Default for Value {
+    fn default() -> Self {
+        Value::Null
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(n: f64) -> Self {
+        Value::Number(n)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(n: i64) -> Self {
+        Value::Integer(n)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        if b {
+            Value::True
+        } else {
+            Value::False
+        }
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    fn from(vec: Vec<Value>) -> Self {
+        Value::Array(vec)
+    }
+}
+
+impl From<Map> for Value {
+    fn from(map: Map) -> Self {
+        Value::Object(map)
+    }
+}
+
+/// Numbers compare by value, so `Integer(1)` equals `Number(1.0)`. Objects
+/// compare equal when they have the same members, in any order.
+impl PartialEq for Value {
+    fn eq(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Object(a), Value::Object(b)) => a == b,
+            (Value::Array(a), Value::Array(b)) => a == b,
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::Number(a), Value::Number(b)) => a == b,
+            (Value::Integer(a), Value::Integer(b)) => a == b,
+            (Value::Integer(i), Value::Number(f)) | (Value::Number(f), Value::Integer(i)) => {
+                int_eq_float(*i, *f)
+            }
+            (Value::RawNumber(s), other) | (other, Value::RawNumber(s)) => {
+                raw_number_value(s) == *other
+            }
+            (Value::True, Value::True)
+            | (Value::False, Value::False)
+            | (Value::Null, Value::Null) => true,
+            _ => false,
+        }
+    }
+}
+
+/// The `Integer` or `Number` that the text of a `RawNumber` stands for, read
+/// the same way as the parser reads numbers. Text that isn't a number gives
+/// NaN.
+pub(crate) fn raw_number_value(s: &str) -> Value {
+    if !s.contains(['.', 'e', 'E']) && s != "-0" {
+        if let Ok(int) = s.parse() {
+            return Value::Integer(int);
+        }
+    }
+
+    Value::Number(s.parse().unwrap_or(f64::NAN))
+}
+
+/// Compares exactly, since casting large integers to f64 rounds them.
+fn int_eq_float(i: i64, f: f64) -> bool {
+    float_to_int(f) == Some(i)
+}
+
+/// The integer a float is exactly equal to, if there is one.
+pub(crate) fn float_to_int(f: f64) -> Option<i64> {
+    // i64::MIN is exactly representable, i64::MAX + 1 is the first float out of range
+    if f >= i64::MIN as f64 && f < -(i64::MIN as f64) && (f as i64) as f64 == f {
+        Some(f as i64)
+    } else {
+        None
+    }
+}
+
+/// `Value` is only truly `Eq` without NaN, which isn't equal to itself. The
+/// parser only produces NaN with `allow_non_finite`.
+impl Eq for Value {}
+
+/// A total order, consistent with `==`, so values can be sorted and used as
+/// `BTreeMap` keys.
+///
+/// Values of different types are ordered by type: null, then booleans, numbers,
+/// strings, arrays and objects. Within a type:
+///
+/// - `false` comes before `true`.
+/// - Numbers compare by value, exactly even between integers and floats, so
+///   `Integer(1)` and `Number(1.0)` are equal, and so are `0` and `-0.0`. NaN
+///   comes after every other number and is equal to itself here, unlike with
+///   `==`.
+/// - Strings compare by their characters' code points.
+/// - Arrays compare element by element, and a prefix comes first.
+/// - Objects compare their members sorted by key, like arrays of key-value
+///   pairs, so the order in which members were inserted doesn't matter. This
+///   sorts both objects for every comparison.
+impl Ord for Value {
+    fn cmp(&self, other: &Value) -> Ordering {
+        match (self, other) {
+            (Value::RawNumber(s), _) => raw_number_value(s).cmp(other),
+            (_, Value::RawNumber(s)) => self.cmp(&raw_number_value(s)),
+            (Value::Object(a), Value::Object(b)) => {
+                let mut a: Vec<_> = a.iter().collect();
+                let mut b: Vec<_> = b.iter().collect();
+                a.sort_unstable_by_key(|&(key, _)| key);
+                b.sort_unstable_by_key(|&(key, _)| key);
+                a.cmp(&b)
+            }
+            (Value::Array(a), Value::Array(b)) => a.cmp(b),
+            (Value::String(a), Value::String(b)) => a.cmp(b),
+            (Value::Integer(a), Value::Integer(b)) => a.cmp(b),
+            (Value::Number(a), Value::Number(b)) => cmp_floats(*a, *b),
+            (Value::Integer(i), Value::Number(f)) => cmp_int_float(*i, *f),
+            (Value::Number(f), Value::Integer(i)) => cmp_int_float(*i, *f).reverse(),
+            _ => type_rank(self).cmp(&type_rank(other)),
+        }
+    }
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The place of a value's type in the order between types. Booleans are
+/// ranked by value too, since they are different variants.
+fn type_rank(value: &Value) -> u8 {
+    match value {
+        Value::Null => 0,
+        Value::False => 1,
+        Value::True => 2,
+        Value::Number(_) | Value::Integer(_) | Value::RawNumber(_) => 3,
+        Value::String(_) => 4,
+        Value::Array(_) => 5,
+        Value::Object(_) => 6,
+    }
+}
+
+/// Like `partial_cmp`, with NaN after everything else.
+fn cmp_floats(a: f64, b: f64) -> Ordering {
+    match a.partial_cmp(&b) {
+        Some(ordering) => ordering,
+        None => a.is_nan().cmp(&b.is_nan()),
+    }
+}
+
+/// Compares exactly, without rounding the integer to a float.
+fn cmp_int_float(i: i64, f: f64) -> Ordering {
+    if f.is_nan() || f >= -(i64::MIN as f64) {
+        return Ordering::Less;
+    }
+    if f < i64::MIN as f64 {
+        return Ordering::Greater;
+    }
+    // in range, casting truncates, which is exact when cast back
+    let whole = f as i64;
+    i.cmp(&whole).then_with(|| cmp_floats(whole as f64, f))
+}
+
+/// Consistent with `==`: an `Integer` and a `Number` of the same value hash the
+/// same, and objects hash the same whatever the order of their members.
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Value::Object(map) => {
+                state.write_u8(0);
+                // combine the members with an operation that ignores order
+                let mut members = 0u64;
+                for member in map.iter() {
+                    let mut hasher = MemberHasher::default();
+                    member.hash(&mut hasher);
+                    members = members.wrapping_add(hasher.finish());
+                }
+                state.write_usize(map.len());
+                state.write_u64(members);
+            }
+            Value::Array(vec) => {
+                state.write_u8(1);
+                vec.hash(state);
+            }
+            Value::String(s) => {
+                state.write_u8(2);
+                s.hash(state);
+            }
+            Value::Integer(i) => {
+                state.write_u8(3);
+                i.hash(state);
+            }
+            Value::Number(f) => match float_to_int(*f) {
+                Some(i) => {
+                    state.write_u8(3);
+                    i.hash(state);
+                }
+                None => {
+                    state.write_u8(4);
+                    f.to_bits().hash(state);
+                }
+            },
+            Value::RawNumber(s) => raw_number_value(s).hash(state),
+            Value::True => state.write_u8(5),
+            Value::False => state.write_u8(6),
+            Value::Null => state.write_u8(7),
+        }
+    }
+}
+
+/// FNV-1a, to hash object members on their own before combining them. It
+/// works without std and doesn't need to resist collision attacks, since the
+/// result is only fed to the caller's hasher.
+struct MemberHasher(u64);
+
+impl Default for MemberHasher {
+    fn default() -> Self {
+        MemberHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for MemberHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= u64::from(b);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+static NULL: Value = Value::Null;
+
+/// Looks up an object member, giving `Null` when the member is missing or the
+/// value isn't an object.
+impl Index<&str> for Value {
+    type Output = Value;
+
+    fn index(&self, key: &str) -> &Value {
+        match self {
+            Value::Object(map) => map.get(key).unwrap_or(&NULL),
+            _ => &NULL,
+        }
+    }
+}
+
+/// Looks up an array element, giving `Null` when the index is out of bounds or
+/// the value isn't an array.
+impl Index<usize> for Value {
+    type Output = Value;
+
+    fn index(&self, index: usize) -> &Value {
+        match self {
+            Value::Array(vec) => vec.get(index).unwrap_or(&NULL),
+            _ => &NULL,
+        }
+    }
+}
+
+/// Parses a value the same way as `json::parse`.
+///
+/// ```
+/// let value: json::Value = "[1,2,3]".parse().unwrap();
+/// assert_eq!(value.to_string(), "[1,2,3]");
+/// ```
+impl FromStr for Value {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        crate::parse(s)
+    }
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "tokio")]
+
+use std::{
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use json::{ErrorKind, Parser, Value};
+
+/// Runs a future that never waits, which is all reading from a slice needs.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    match future
+        .as_mut()
+        .poll(&mut Context::from_waker(Waker::noop()))
+    {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("reading from a slice doesn't wait"),
+    }
+}
+
+#[test]
+fn parses_what_it_reads() {
+    let value = block_on(json::from_async_reader(&b"{\"a\":[1,2]}"[..])).unwrap();
+    assert_eq!(value["a"][1], Value::Integer(2));
+
+    let err = block_on(json::from_async_reader(&b"[1,"[..])).unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::UnexpectedEof));
+}
+
+#[test]
+fn stops_reading_past_max_input_bytes() {
+    let parser = Parser::new().max_input_bytes(5);
+    assert!(block_on(parser.parse_async_reader(&b"[1,2]"[..])).is_ok());
+
+    let err = block_on(parser.parse_async_reader(&b"[1,2,3]"[..])).unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::SizeLimitExceeded));
+    assert_eq!((err.line, err.column, err.byte_offset), (1, 1, 0));
+}
@@ -0,0 +1,54 @@
+use json::{ErrorKind, Parser, Value};
+
+fn number(input: &str) -> Value {
+    json::parse(input).unwrap()
+}
+
+#[test]
+fn negative_fractions() {
+    assert_eq!(number("-1.5"), Value::Number(-1.5));
+    assert_eq!(number("-0.25"), Value::Number(-0.25));
+    assert_eq!(number("-10.75"), Value::Number(-10.75));
+}
+
+#[test]
+fn exponents() {
+    assert_eq!(number("1.5e2"), Value::Number(150.0));
+    assert_eq!(number("6.022e23"), Value::Number(6.022e23));
+    assert_eq!(number("1e-7"), Value::Number(0.0000001));
+    assert_eq!(number("2.5E3"), Value::Number(2500.0));
+}
+
+#[test]
+fn long_integers_do_not_wrap() {
+    assert_eq!(number("12345678901234"), Value::Integer(12_345_678_901_234));
+    // 20 digits is past i64, so this becomes the nearest double
+    assert_eq!(
+        number("12345678901234567890"),
+        Value::Number(12_345_678_901_234_567_890.0)
+    );
+    assert_eq!(
+        number("-99999999999999999999"),
+        Value::Number(-99_999_999_999_999_999_999.0)
+    );
+}
+
+#[test]
+fn leading_zeros() {
+    for input in ["01", "-00", "012.3"] {
+        let err = json::parse(input).unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::InvalidNumber), "{}", input);
+    }
+    assert_eq!(number("0"), Value::Integer(0));
+    assert_eq!(number("0.0"), Value::Number(0.0));
+    assert_eq!(number("10"), Value::Integer(10));
+}
+
+#[test]
+fn raw_numbers_round_trip_byte_for_byte() {
+    let parser = Parser::new().raw_numbers(true);
+    for input in ["1.0", "1E3", "100.00", "-0", r#"[1.0,{"a":1E3},-0]"#] {
+        let value = parser.parse(input).unwrap();
+        assert_eq!(value.to_string(), input);
+    }
+}
@@ -0,0 +1,138 @@
+//! Serializes random `Value` trees and parses them back, checking that they
+//! come back equal and serialize to the same text again.
+//!
+//! The trees come from a seeded xorshift generator, so a failure reproduces
+//! by its seed. Left out on purpose:
+//!
+//! - Non-finite numbers, which JSON can't represent and which are written
+//!   as `null`.
+//! - `RawNumber`, which only the `raw_numbers` option produces.
+//! - `Format::Canonical`, which treats every number as a double, so
+//!   integers beyond 2^53 get rounded.
+//!
+//! Whole floats are written without a fraction and come back as an
+//! `Integer`, which compares equal, so the variant isn't checked.
+#![cfg(feature = "std")]
+
+use json::{Map, Value};
+
+const SEEDS: u64 = 1000;
+const MAX_DEPTH: u32 = 4;
+
+/// xorshift64*, see Vigna, "An experimental exploration of Marsaglia's
+/// xorshift generators, scrambled".
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // the state must not be zero
+        Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
+
+fn value(rng: &mut Rng, depth: u32) -> Value {
+    let kinds = if depth < MAX_DEPTH { 8 } else { 6 };
+    match rng.below(kinds) {
+        0 => Value::Null,
+        1 => Value::True,
+        2 => Value::False,
+        3 => Value::Integer(integer(rng)),
+        4 => Value::Number(number(rng)),
+        5 => Value::String(string(rng)),
+        6 => Value::Array((0..rng.below(5)).map(|_| value(rng, depth + 1)).collect()),
+        _ => {
+            let mut map = Map::new();
+            for _ in 0..rng.below(5) {
+                map.insert(string(rng), value(rng, depth + 1));
+            }
+            Value::Object(map)
+        }
+    }
+}
+
+fn integer(rng: &mut Rng) -> i64 {
+    match rng.below(3) {
+        0 => rng.below(100) as i64 - 50,
+        1 => [i64::MIN, i64::MAX, 0, -1][rng.below(4) as usize],
+        _ => rng.next() as i64,
+    }
+}
+
+fn number(rng: &mut Rng) -> f64 {
+    let n = match rng.below(4) {
+        0 => (rng.below(2000) as f64 - 1000.0) / 8.0,
+        // whole floats, mostly past 2^53
+        1 => rng.next() as i64 as f64,
+        2 => [
+            0.0,
+            -0.0,
+            f64::MIN_POSITIVE,
+            f64::MAX,
+            f64::EPSILON,
+            1e300,
+            5e-324,
+        ][rng.below(7) as usize],
+        _ => f64::from_bits(rng.next()),
+    };
+    if n.is_finite() {
+        n
+    } else {
+        0.5
+    }
+}
+
+fn string(rng: &mut Rng) -> String {
+    (0..rng.below(12))
+        .map(|_| match rng.below(4) {
+            // control characters, quotes and backslashes need escaping
+            0 => ['\0', '\u{1f}', '\n', '\t', '"', '\\', '/', '\u{7f}'][rng.below(8) as usize],
+            1 => char::from(b' ' + rng.below(95) as u8),
+            2 => char::from_u32(rng.below(0xD800) as u32).unwrap(),
+            _ => char::from_u32(0x10000 + rng.below(0x100000) as u32).unwrap(),
+        })
+        .collect()
+}
+
+fn assert_same(original: &Value, text: &str, via: &str, seed: u64) {
+    let parsed = [
+        json::parse(text).unwrap(),
+        json::parse_bytes(text.as_bytes()).unwrap(),
+        json::from_reader(text.as_bytes()).unwrap(),
+    ];
+    for again in parsed {
+        assert_eq!(again, *original, "seed {} via {}", seed, via);
+        assert_eq!(
+            again.to_string(),
+            original.to_string(),
+            "seed {} via {}",
+            seed,
+            via
+        );
+    }
+}
+
+#[test]
+fn random_values_round_trip() {
+    for seed in 0..SEEDS {
+        let original = value(&mut Rng::new(seed), 0);
+
+        assert_same(&original, &original.to_string(), "to_string", seed);
+        let pretty = json::to_string_pretty(&original, 2);
+        assert_same(&original, &pretty, "to_string_pretty", seed);
+        let mut written = Vec::new();
+        json::to_writer(&mut written, &original).unwrap();
+        let written = String::from_utf8(written).unwrap();
+        assert_same(&original, &written, "to_writer", seed);
+    }
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "serde")]
+
+use json::Value;
+
+const DOCUMENT: &str = r#"{"name":"a \"b\"","tags":["x",null,true],"n":-3,"f":1.5}"#;
+
+#[test]
+fn serializes_through_serde_json() {
+    let value = json::parse(DOCUMENT).unwrap();
+    let text = serde_json::to_string(&value).unwrap();
+    let expected: serde_json::Value = serde_json::from_str(DOCUMENT).unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&text).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn deserializes_through_serde_json() {
+    let value: Value = serde_json::from_str(DOCUMENT).unwrap();
+    assert_eq!(value, json::parse(DOCUMENT).unwrap());
+    assert_eq!(value["n"], Value::Integer(-3));
+
+    let big: Value = serde_json::from_str("18446744073709551615").unwrap();
+    assert_eq!(big, Value::Number(18446744073709551615.0));
+}
+
+#[test]
+fn converts_to_and_from_serde_json_value() {
+    let value = json::parse(DOCUMENT).unwrap();
+    let converted = serde_json::Value::from(value.clone());
+    assert_eq!(
+        converted,
+        serde_json::from_str::<serde_json::Value>(DOCUMENT).unwrap()
+    );
+    assert_eq!(Value::from(converted), value);
+
+    let infinite: serde_json::Value = Value::Number(f64::INFINITY).into();
+    assert_eq!(infinite, serde_json::Value::Null);
+}
+
+#[test]
+fn raw_numbers_serialize_as_numbers() {
+    let value = json::Parser::new()
+        .raw_numbers(true)
+        .parse("[1.50,10,-0]")
+        .unwrap();
+    assert_eq!(serde_json::to_string(&value).unwrap(), "[1.5,10,-0.0]");
+}
@@ -0,0 +1,98 @@
+use json::{ErrorKind, Event};
+
+#[test]
+fn stream_skips_bom_before_first_value_only() {
+    let values: Vec<_> = json::parse_stream("\u{FEFF}{\"a\":1} [2]").collect();
+    assert_eq!(values.len(), 2);
+    assert!(values.iter().all(Result::is_ok));
+
+    let mut values = json::parse_stream("{\"a\":1}\u{FEFF}{\"b\":2}");
+    assert!(values.next().unwrap().is_ok());
+    let err = values.next().unwrap().unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::UnexpectedChar('\u{FEFF}')));
+    assert_eq!((err.line, err.column, err.byte_offset), (1, 8, 7));
+    assert!(values.next().is_none());
+}
+
+#[test]
+fn lines_give_one_value_per_line() {
+    let input = "{\"a\":1}\n{\"b\":2}\r\n{\"c\":3}\n";
+    let values: Vec<_> = json::parse_lines(input).map(Result::unwrap).collect();
+    assert_eq!(values.len(), 3);
+    assert_eq!(values[0]["a"], json::Value::Integer(1));
+    assert_eq!(values[1]["b"], json::Value::Integer(2));
+    assert_eq!(values[2]["c"], json::Value::Integer(3));
+}
+
+#[test]
+fn lines_skip_blank_lines() {
+    let input = "\n[1]\n\n  \t\n[2]\n\n";
+    let values: Vec<_> = json::parse_lines(input).map(Result::unwrap).collect();
+    assert_eq!(values.len(), 2);
+}
+
+#[test]
+fn lines_skip_bom_at_start_of_input_only() {
+    assert!(json::parse_lines("\u{FEFF}[1]\n[2]").all(|r| r.is_ok()));
+
+    let mut values = json::parse_lines("[1]\n\u{FEFF}[2]");
+    assert!(values.next().unwrap().is_ok());
+    let err = values.next().unwrap().unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::UnexpectedChar('\u{FEFF}')));
+    assert_eq!((err.line, err.column, err.byte_offset), (2, 1, 4));
+}
+
+#[test]
+fn prefix_gives_the_rest_after_trailing_whitespace() {
+    for (input, value, offset) in [
+        ("{\"a\":1}  \r\nREST", "{\"a\":1}", 11),
+        ("[1] [2]", "[1]", 4),
+        ("12abc", "12", 2),
+        (" true", "true", 5),
+        ("\u{FEFF}[1]x", "[1]", 6),
+    ] {
+        let (parsed, rest) = json::parse_prefix(input).unwrap();
+        assert_eq!(parsed.to_string(), value, "{:?}", input);
+        assert_eq!(input.len() - rest.len(), offset, "{:?}", input);
+        assert_eq!(rest, &input[offset..]);
+    }
+
+    let err = json::parse_prefix("[1").unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::UnexpectedEof));
+}
+
+#[test]
+fn events_of_a_nested_document() {
+    let input = r#"{"a":[1,2.5,"x",{"b":null}],"c":true}"#;
+    let events: Vec<Event> = json::events(input).map(Result::unwrap).collect();
+    assert_eq!(
+        events,
+        [
+            Event::StartObject,
+            Event::Key("a".to_string()),
+            Event::StartArray,
+            Event::Integer(1),
+            Event::Number(2.5),
+            Event::Str("x".to_string()),
+            Event::StartObject,
+            Event::Key("b".to_string()),
+            Event::Null,
+            Event::EndObject,
+            Event::EndArray,
+            Event::Key("c".to_string()),
+            Event::Bool(true),
+            Event::EndObject,
+        ]
+    );
+}
+
+#[test]
+fn events_end_with_the_error() {
+    let mut events = json::events("[1,]");
+    assert_eq!(events.next().unwrap().unwrap(), Event::StartArray);
+    assert_eq!(events.next().unwrap().unwrap(), Event::Integer(1));
+    let err = events.next().unwrap().unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::UnexpectedChar(']')));
+    assert_eq!((err.line, err.column, err.byte_offset), (1, 4, 3));
+    assert!(events.next().is_none());
+}
@@ -0,0 +1,68 @@
+use std::borrow::Cow;
+
+use json::{BorrowedValue, ErrorKind, Value};
+
+#[test]
+fn form_feed_escape() {
+    let value = json::parse(r#""a\fb""#).unwrap();
+    assert_eq!(value.as_str(), Some("a\u{c}b"));
+}
+
+#[test]
+fn control_characters_round_trip() {
+    let original: String = ('\0'..=' ').chain(['\u{7f}', '"', '\\', 'a']).collect();
+    let text = Value::String(original.clone()).to_string();
+    assert!(!text.chars().any(|c| c < ' '), "{:?}", text);
+    assert_eq!(json::parse(&text).unwrap().as_str(), Some(&*original));
+}
+
+#[test]
+fn literal_newline_in_string() {
+    let err = json::parse("\"a\nb\"").unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::InvalidControlChar('\n')));
+    assert_eq!((err.line, err.column), (1, 3));
+}
+
+#[test]
+fn max_string_length_counts_bytes_as_written() {
+    let parser = json::Parser::new().max_string_length(3);
+    for input in [r#"["abc"]"#, r#"{"abc":1}"#, r#"["aé"]"#] {
+        assert!(parser.parse(input).is_ok(), "{}", input);
+    }
+
+    // "\n" is two bytes in the input, though it unescapes to one
+    for input in [r#"["abcd"]"#, r#"{"abcd":1}"#, r#"["a\nb"]"#] {
+        let err = parser.parse(input).unwrap_err();
+        assert!(
+            matches!(err.kind, ErrorKind::SizeLimitExceeded),
+            "{}",
+            input
+        );
+        // the error points at the opening quote
+        assert_eq!((err.line, err.column, err.byte_offset), (1, 2, 1));
+    }
+}
+
+#[test]
+fn borrowed_strings_point_into_the_input() {
+    let input = r#"{"plain":"text","esc\"aped":"a\nb"}"#;
+    let members = match json::parse_borrowed(input).unwrap() {
+        BorrowedValue::Object(members) => members,
+        other => panic!("{:?}", other),
+    };
+
+    let (key, value) = &members[0];
+    assert!(matches!(key, Cow::Borrowed("plain")));
+    match value {
+        BorrowedValue::String(Cow::Borrowed(s)) => {
+            assert_eq!(*s, "text");
+            assert!(input.as_bytes().as_ptr_range().contains(&s.as_ptr()));
+        }
+        other => panic!("{:?}", other),
+    }
+
+    // an escape means unescaping into a new string
+    let (key, value) = &members[1];
+    assert!(matches!(key, Cow::Owned(key) if key == "esc\"aped"));
+    assert!(matches!(value, BorrowedValue::String(Cow::Owned(s)) if s == "a\nb"));
+}
@@ -0,0 +1,218 @@
+use std::sync::Arc;
+
+use json::{DuplicateKeys, ErrorKind, Parser, Value};
+
+#[test]
+fn deep_nesting_drops_without_overflow() {
+    let depth = 50_000;
+    let parser = Parser::new().with_max_depth(usize::MAX);
+
+    let arrays = "[".repeat(depth) + &"]".repeat(depth);
+    parser.parse(&arrays).unwrap().drop_deep();
+
+    let objects = r#"{"a":"#.repeat(depth) + "null" + &"}".repeat(depth);
+    parser.parse(&objects).unwrap().drop_deep();
+
+    // what was parsed before an error is freed the same way
+    let err = parser.parse(&(arrays.clone() + "x")).unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::TrailingData));
+    let err = parser
+        .parse(&("[".to_string() + &arrays + ","))
+        .unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::UnexpectedEof));
+}
+
+#[test]
+fn single_quoted_root_is_a_scalar() {
+    let parser = Parser::new()
+        .require_structured_root(true)
+        .allow_single_quotes(true);
+    let err = parser.parse("'abc'").unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::RootMustBeObjectOrArray));
+    assert!(parser.parse("['abc']").is_ok());
+
+    let err = Parser::new()
+        .require_structured_root(true)
+        .parse("'abc'")
+        .unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::UnexpectedChar('\'')));
+}
+
+#[test]
+fn commas_only_between_elements() {
+    for input in ["[,1]", "[1,]", "{,\"a\":1}", "{\"a\":1,}"] {
+        assert!(json::parse(input).is_err(), "{} should fail", input);
+    }
+    for input in ["[1,2]", "{\"a\":1,\"b\":2}"] {
+        assert!(json::parse(input).is_ok(), "{} should parse", input);
+    }
+}
+
+#[test]
+fn leading_byte_order_mark() {
+    let value = json::parse("\u{FEFF}{\"a\":1}").unwrap();
+    assert_eq!(value["a"], json::Value::Integer(1));
+
+    for input in [" \u{FEFF}{\"a\":1}", "{\"a\":1}\u{FEFF}", "[\u{FEFF}1]"] {
+        assert!(json::parse(input).is_err(), "{:?} should fail", input);
+    }
+}
+
+#[test]
+fn depth_limit() {
+    let err = json::parse(&"[".repeat(10_000)).unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::DepthLimitExceeded));
+    // the default limit is 128
+    assert_eq!(err.column, 129);
+}
+
+#[test]
+fn trailing_commas_only_when_allowed() {
+    let lenient = Parser::new().allow_trailing_commas(true);
+    for input in ["[1,2,]", "{\"a\":1,}"] {
+        assert!(json::parse(input).is_err(), "{} should fail", input);
+        assert!(lenient.parse(input).is_ok(), "{} should parse", input);
+    }
+    for input in ["[1,,]", "[,]"] {
+        assert!(lenient.parse(input).is_err(), "{} should fail", input);
+    }
+}
+
+#[test]
+fn keys_must_be_strings() {
+    for (input, column) in [
+        ("{port: 8080}", 2),
+        (r#"{123: "x"}"#, 2),
+        (r#"{"a":1, b:2}"#, 9),
+    ] {
+        let err = json::parse(input).unwrap_err();
+        assert!(
+            matches!(err.kind, ErrorKind::ExpectedStringKey),
+            "{}",
+            input
+        );
+        assert_eq!(
+            (err.line, err.column, err.byte_offset),
+            (1, column, column - 1)
+        );
+    }
+
+    let strict = Parser::new().allow_unquoted_keys(false);
+    let err = strict.parse("{port: 8080}").unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::ExpectedStringKey));
+    let relaxed = Parser::new().allow_unquoted_keys(true);
+    assert_eq!(
+        relaxed.parse("{port: 8080}").unwrap()["port"],
+        Value::Integer(8080)
+    );
+    // a relaxed key is still an identifier, not a number
+    let err = relaxed.parse(r#"{123: "x"}"#).unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::ExpectedStringKey));
+}
+
+#[test]
+fn max_input_bytes_is_inclusive() {
+    let parser = Parser::new().max_input_bytes(7);
+    assert!(parser.parse("[1,2,3]").is_ok());
+
+    for err in [
+        parser.parse("[1,2,30]").unwrap_err(),
+        parser.parse("[1,2,3] ").unwrap_err(),
+        parser.parse_bytes(b"[1,2,30]").unwrap_err(),
+    ] {
+        assert!(matches!(err.kind, ErrorKind::SizeLimitExceeded));
+        assert_eq!((err.line, err.column, err.byte_offset), (1, 1, 0));
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn max_input_bytes_cuts_off_a_stream() {
+    let parser = Parser::new().max_input_bytes(7);
+    assert!(parser.parse_reader(&b"[1,2,3]"[..]).is_ok());
+
+    // the stream is cut off at the first byte past the limit
+    let err = parser.parse_reader(&b"[1,2,30]"[..]).unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::SizeLimitExceeded));
+    assert_eq!(err.byte_offset, 7);
+}
+
+#[test]
+fn interned_keys_share_one_allocation() {
+    let input = r#"[{"id":1,"name":"a"},{"id":2,"name":"b"},{"name":"c"}]"#;
+    let keys = |parser: Parser| -> Vec<Arc<str>> {
+        let records = match parser.parse(input).unwrap() {
+            Value::Array(records) => records,
+            other => panic!("{:?}", other),
+        };
+        records
+            .into_iter()
+            .flat_map(|record| match record {
+                Value::Object(map) => map.into_iter().map(|(key, _)| key),
+                other => panic!("{:?}", other),
+            })
+            .filter(|key| &**key == "name")
+            .collect()
+    };
+
+    let interned = keys(Parser::new().intern_keys(true));
+    assert_eq!(interned.len(), 3);
+    assert!(interned.iter().all(|key| Arc::ptr_eq(key, &interned[0])));
+
+    let separate = keys(Parser::new());
+    assert!(!Arc::ptr_eq(&separate[0], &separate[1]));
+}
+
+#[test]
+fn validate_agrees_with_parse() {
+    let inputs = [
+        r#"{"a":[1,2,{"b":null}],"c":"d"}"#,
+        r#"{"a":1,"a":2}"#,
+        "[1,2,]",
+        "[01]",
+        r#"{"a" 1}"#,
+        r#"["\x"]"#,
+        "[1] x",
+        "",
+        "[[[[[[[[[[1]]]]]]]]]]",
+    ];
+    for policy in [
+        DuplicateKeys::Last,
+        DuplicateKeys::First,
+        DuplicateKeys::Error,
+    ] {
+        let parser = Parser::new().duplicate_keys(policy).with_max_depth(5);
+        for input in inputs {
+            let parsed = parser.parse(input).map(drop).map_err(|e| e.to_string());
+            let validated = parser.validate(input).map_err(|e| e.to_string());
+            assert_eq!(validated, parsed, "{} with {:?}", input, policy);
+        }
+    }
+}
+
+#[test]
+fn missing_comma_between_members() {
+    for (input, column) in [
+        (r#"{"a":1 2}"#, 8),
+        (r#"{"a":1 true}"#, 8),
+        (r#"{"a":1 "b":2}"#, 8),
+        (r#"{"a":[1] {"b":2}}"#, 10),
+        ("[1 2]", 4),
+        ("[[] null]", 5),
+    ] {
+        let err = json::parse(input).unwrap_err();
+        assert!(
+            matches!(err.kind, ErrorKind::MissingComma),
+            "{}: {:?}",
+            input,
+            err.kind
+        );
+        assert_eq!((err.line, err.column), (1, column), "{}", input);
+        let err = json::validate(input).unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::MissingComma), "{}", input);
+    }
+
+    // anything that can't start a value is just unexpected
+    let err = json::parse(r#"{"a":1 :}"#).unwrap_err();
+    assert!(matches!(err.kind, ErrorKind::UnexpectedChar(':')));
+}
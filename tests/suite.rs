@@ -0,0 +1,170 @@
+//! Runs the documents in `suite/`, which are the 318 cases of JSONTestSuite's
+//! `test_parsing` directory under their upstream names: `y_` ones must
+//! parse, `n_` ones must fail, and `i_` ones, whose outcome the grammar
+//! leaves to the parser, must do what `IMPLEMENTATION` says.
+//!
+//! The suite can't be fetched where this crate is built, so the files were
+//! recreated by hand from the upstream ones. The test counts them, so a
+//! case that goes missing doesn't go unnoticed.
+//!
+//! Every document goes through both `parse_bytes` and `from_reader`.
+#![cfg(feature = "std")]
+
+use std::{fs, path::Path};
+
+use json::{ErrorKind, ParseError, Value};
+
+type Outcome = Result<Value, ParseError>;
+type Check = fn(&Outcome) -> bool;
+
+/// What each `i_` document gives.
+const IMPLEMENTATION: &[(&str, Check)] = &[
+    // numbers past the range of f64 become infinite, or zero
+    ("i_number_double_huge_neg_exp", zero),
+    ("i_number_huge_exp", infinite),
+    ("i_number_neg_int_huge_exp", infinite),
+    ("i_number_pos_double_huge_exp", infinite),
+    ("i_number_real_neg_overflow", infinite),
+    ("i_number_real_pos_overflow", infinite),
+    ("i_number_real_underflow", zero),
+    // integers too big for i64 become floats
+    ("i_number_too_big_neg_int", float),
+    ("i_number_too_big_pos_int", float),
+    ("i_number_very_big_negative_int", float),
+    // lone surrogates can't make a char
+    ("i_object_key_lone_2nd_surrogate", invalid_escape),
+    ("i_string_1st_surrogate_but_2nd_missing", invalid_escape),
+    ("i_string_1st_valid_surrogate_2nd_invalid", invalid_escape),
+    (
+        "i_string_incomplete_surrogate_and_escape_valid",
+        invalid_escape,
+    ),
+    ("i_string_incomplete_surrogate_pair", invalid_escape),
+    (
+        "i_string_incomplete_surrogates_escape_valid",
+        invalid_escape,
+    ),
+    ("i_string_invalid_lonely_surrogate", invalid_escape),
+    ("i_string_invalid_surrogate", invalid_escape),
+    ("i_string_inverted_surrogates_U+1D11E", invalid_escape),
+    ("i_string_lone_second_surrogate", invalid_escape),
+    // only UTF-8 is read
+    ("i_string_UTF-16LE_with_BOM", invalid_utf8),
+    ("i_string_UTF-8_invalid_sequence", invalid_utf8),
+    ("i_string_invalid_utf-8", invalid_utf8),
+    ("i_string_lone_utf8_continuation_byte", invalid_utf8),
+    ("i_string_overlong_sequence_2_bytes", invalid_utf8),
+    ("i_string_truncated-utf-8", invalid_utf8),
+    ("i_string_UTF8_surrogate_U+D800", invalid_utf8),
+    ("i_string_iso_latin_1", invalid_utf8),
+    ("i_string_not_in_unicode_range", invalid_utf8),
+    ("i_string_overlong_sequence_6_bytes", invalid_utf8),
+    ("i_string_overlong_sequence_6_bytes_null", invalid_utf8),
+    // UTF-16 without a byte order mark is read as UTF-8, which fails on the
+    // NUL bytes or the lone byte of the é, whichever is checked first
+    ("i_string_utf16BE_no_BOM", Result::is_err),
+    ("i_string_utf16LE_no_BOM", Result::is_err),
+    // past the default limit of 128
+    ("i_structure_500_nested_arrays", too_deep),
+    // a UTF-8 byte order mark is skipped
+    ("i_structure_UTF-8_BOM_empty_object", |outcome| {
+        outcome.is_ok()
+    }),
+];
+
+fn first(outcome: &Outcome) -> Option<f64> {
+    match outcome {
+        Ok(Value::Array(vec)) => match vec.first() {
+            Some(Value::Number(n)) => Some(*n),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn infinite(outcome: &Outcome) -> bool {
+    first(outcome).is_some_and(f64::is_infinite)
+}
+
+fn zero(outcome: &Outcome) -> bool {
+    first(outcome) == Some(0.0)
+}
+
+fn float(outcome: &Outcome) -> bool {
+    first(outcome).is_some_and(f64::is_finite)
+}
+
+fn invalid_escape(outcome: &Outcome) -> bool {
+    matches!(
+        outcome,
+        Err(ParseError {
+            kind: ErrorKind::InvalidEscape,
+            ..
+        })
+    )
+}
+
+fn invalid_utf8(outcome: &Outcome) -> bool {
+    matches!(
+        outcome,
+        Err(ParseError {
+            kind: ErrorKind::InvalidUtf8,
+            ..
+        })
+    )
+}
+
+fn too_deep(outcome: &Outcome) -> bool {
+    matches!(
+        outcome,
+        Err(ParseError {
+            kind: ErrorKind::DepthLimitExceeded,
+            ..
+        })
+    )
+}
+
+#[test]
+fn suite() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/suite");
+    let mut failures = Vec::new();
+    let mut implementation_seen = Vec::new();
+    let (mut accepted, mut rejected) = (0, 0);
+
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        let name = path.file_stem().unwrap().to_str().unwrap().to_string();
+        let input = fs::read(&path).unwrap();
+
+        for (via, outcome) in [
+            ("parse_bytes", json::parse_bytes(&input)),
+            ("from_reader", json::from_reader(&input[..])),
+        ] {
+            let passed = match &name[..2] {
+                "y_" => outcome.is_ok(),
+                "n_" => outcome.is_err(),
+                "i_" => match IMPLEMENTATION.iter().find(|(case, _)| *case == name) {
+                    Some((_, expected)) => expected(&outcome),
+                    None => panic!("{} isn't listed in IMPLEMENTATION", name),
+                },
+                _ => panic!("{} doesn't start with y_, n_ or i_", name),
+            };
+            if !passed {
+                failures.push(format!("{} via {}: {:?}", name, via, outcome));
+            }
+        }
+        match &name[..2] {
+            "y_" => accepted += 1,
+            "n_" => rejected += 1,
+            _ => implementation_seen.push(name),
+        }
+    }
+
+    assert!(failures.is_empty(), "{:#?}", failures);
+    assert_eq!(
+        (accepted, rejected, implementation_seen.len()),
+        (95, 188, 35),
+        "test_parsing has 95 y_, 188 n_ and 35 i_ cases"
+    );
+    assert_eq!(implementation_seen.len(), IMPLEMENTATION.len());
+}
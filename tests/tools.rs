@@ -0,0 +1,125 @@
+use json::{ArrayMerge, Change, Value};
+
+fn parse(input: &str) -> Value {
+    json::parse(input).unwrap()
+}
+
+#[test]
+fn merge_patch_rfc_7386_examples() {
+    let cases = [
+        (r#"{"a":"b"}"#, r#"{"a":"c"}"#, r#"{"a":"c"}"#),
+        (r#"{"a":"b"}"#, r#"{"b":"c"}"#, r#"{"a":"b","b":"c"}"#),
+        (r#"{"a":"b"}"#, r#"{"a":null}"#, r#"{}"#),
+        (r#"{"a":"b","b":"c"}"#, r#"{"a":null}"#, r#"{"b":"c"}"#),
+        (r#"{"a":["b"]}"#, r#"{"a":"c"}"#, r#"{"a":"c"}"#),
+        (r#"{"a":"c"}"#, r#"{"a":["b"]}"#, r#"{"a":["b"]}"#),
+        (
+            r#"{"a":{"b":"c"}}"#,
+            r#"{"a":{"b":"d","c":null}}"#,
+            r#"{"a":{"b":"d"}}"#,
+        ),
+        (r#"{"a":[{"b":"c"}]}"#, r#"{"a":[1]}"#, r#"{"a":[1]}"#),
+        (r#"["a","b"]"#, r#"["c","d"]"#, r#"["c","d"]"#),
+        (r#"{"a":"b"}"#, r#"["c"]"#, r#"["c"]"#),
+        (r#"{"a":"foo"}"#, r#"null"#, r#"null"#),
+        (r#"{"a":"foo"}"#, r#""bar""#, r#""bar""#),
+        (r#"{"e":null}"#, r#"{"a":1}"#, r#"{"e":null,"a":1}"#),
+        (r#"[1,2]"#, r#"{"a":"b","c":null}"#, r#"{"a":"b"}"#),
+        (
+            r#"{}"#,
+            r#"{"a":{"bb":{"ccc":null}}}"#,
+            r#"{"a":{"bb":{}}}"#,
+        ),
+    ];
+    for (target, patch, expected) in cases {
+        let mut value = parse(target);
+        json::merge_patch(&mut value, &parse(patch));
+        assert_eq!(value, parse(expected), "{} patched with {}", target, patch);
+    }
+}
+
+#[test]
+fn deep_merge_nested_objects() {
+    let base = parse(r#"{"server": {"http": {"port": 80, "host": "localhost"}, "workers": 4}}"#);
+    let overlay = parse(r#"{"server": {"http": {"port": 8080, "tls": true}}, "debug": true}"#);
+    let merged = json::deep_merge(base, overlay, ArrayMerge::Replace);
+    let expected = parse(
+        r#"{
+            "server": {
+                "http": {"port": 8080, "host": "localhost", "tls": true},
+                "workers": 4
+            },
+            "debug": true
+        }"#,
+    );
+    assert_eq!(merged, expected);
+}
+
+#[test]
+fn deep_merge_arrays() {
+    let base = parse(r#"{"a": {"tags": [1, 2]}}"#);
+    let overlay = parse(r#"{"a": {"tags": [3]}}"#);
+
+    let merged = json::deep_merge(base.clone(), overlay.clone(), ArrayMerge::Concat);
+    assert_eq!(merged, parse(r#"{"a": {"tags": [1, 2, 3]}}"#));
+
+    let merged = json::deep_merge(base, overlay, ArrayMerge::Replace);
+    assert_eq!(merged, parse(r#"{"a": {"tags": [3]}}"#));
+}
+
+#[test]
+fn diff_reports_modified_and_added_members() {
+    let a = parse(r#"{"a":1,"b":2}"#);
+    let b = parse(r#"{"a":1,"b":3,"c":4}"#);
+    let changes = json::diff(&a, &b);
+    assert_eq!(changes.len(), 2);
+    assert!(changes.contains(&Change::Modified {
+        path: "/b".to_string(),
+        old: Value::Integer(2),
+        new: Value::Integer(3),
+    }));
+    assert!(changes.contains(&Change::Added {
+        path: "/c".to_string(),
+        value: Value::Integer(4),
+    }));
+}
+
+#[test]
+fn query_selectors() {
+    let doc = parse(r#"{"n": 3, "servers": [{"host": "a", "tags": [1, 2]}, {"host": "b"}]}"#);
+    let query = |path| -> Vec<String> {
+        let found = doc.query(path).unwrap();
+        found.into_iter().map(Value::to_string).collect()
+    };
+
+    assert_eq!(query("$"), [doc.to_string()]);
+    assert_eq!(query("$.n"), ["3"]);
+    assert_eq!(query("$.servers[1].host"), [r#""b""#]);
+    assert_eq!(query("$.servers[*].host"), [r#""a""#, r#""b""#]);
+    assert_eq!(query("$.servers.*.host"), [r#""a""#, r#""b""#]);
+    assert_eq!(query("$.servers[0].tags[*]"), ["1", "2"]);
+    assert_eq!(
+        query("$.*"),
+        ["3".to_string(), query("$.servers")[0].clone()]
+    );
+    // steps that don't apply select nothing
+    assert!(query("$.servers[2]").is_empty());
+    assert!(query("$.servers.host").is_empty());
+    assert!(query("$.n[0]").is_empty());
+}
+
+#[test]
+fn query_rejects_unsupported_syntax() {
+    let doc = parse(r#"{"a": [1]}"#);
+    for (path, offset) in [
+        ("a", 0),
+        ("$..a", 2),
+        ("$.a[-1]", 4),
+        ("$.a[0:1]", 4),
+        ("$.a[0", 5),
+        ("$a", 1),
+    ] {
+        let err = doc.query(path).unwrap_err();
+        assert_eq!(err, json::QueryError { offset }, "{}", path);
+    }
+}